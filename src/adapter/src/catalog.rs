@@ -5737,6 +5737,10 @@ impl mz_sql::catalog::CatalogSchema for Schema {
     fn has_items(&self) -> bool {
         !self.items.is_empty()
     }
+
+    fn item_ids(&self) -> Box<dyn Iterator<Item = GlobalId> + '_> {
+        Box::new(self.items.values().copied())
+    }
 }
 
 impl mz_sql::catalog::CatalogRole for Role {
@@ -0,0 +1,187 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+
+use crate::utils::REDACTED;
+
+/// Environment variable with the path of the audit log, which is
+/// only written to when set.
+pub(crate) const AUDIT_LOG_VAR: &str = "MZ_AUDIT_LOG";
+
+/// Options whose value may hold a secret, e.g. a `CREATE SECRET` statement.
+const SECRET_OPTIONS: [&str; 5] = ["-c", "--command", "--set", "--param", "--secret"];
+
+/// Prefix of the app passwords.
+const APP_PASSWORD_PREFIX: &str = "mzp_";
+
+/// Returns the end of the first short option that may hold a secret in a
+/// group of short options, e.g. `-c` in `-vc`. Its value follows either in
+/// the same argument, e.g. `-cCREATE SECRET ...`, or in the next one.
+fn short_secret_option_end(arg: &str) -> Option<usize> {
+    let options = arg
+        .strip_prefix('-')
+        .filter(|options| !options.starts_with('-'))?;
+    options.char_indices().find_map(|(i, c)| {
+        let option = format!("-{}", c);
+        SECRET_OPTIONS
+            .contains(&option.as_str())
+            .then(|| "-".len() + i + c.len_utf8())
+    })
+}
+
+/// Replaces the values of the options that may hold a secret,
+/// and anything that looks like an app password.
+fn scrub_args(args: &[String]) -> Vec<String> {
+    let mut scrubbed = vec![];
+    let mut secret_value = false;
+    for arg in args {
+        if secret_value || arg.contains(APP_PASSWORD_PREFIX) {
+            secret_value = false;
+            scrubbed.push(REDACTED.to_string());
+        } else if let Some((option, _)) = arg
+            .split_once('=')
+            .filter(|(option, _)| SECRET_OPTIONS.contains(option))
+        {
+            scrubbed.push(format!("{}={}", option, REDACTED));
+        } else if let Some(end) = short_secret_option_end(arg) {
+            if end < arg.len() {
+                scrubbed.push(format!("{}{}", &arg[..end], REDACTED));
+            } else {
+                secret_value = true;
+                scrubbed.push(arg.clone());
+            }
+        } else {
+            secret_value = SECRET_OPTIONS.contains(&arg.as_str());
+            scrubbed.push(arg.clone());
+        }
+    }
+    scrubbed
+}
+
+/// Formats the audit log line of an invocation: when it ended, in seconds
+/// since the Unix epoch, its exit code and its scrubbed arguments.
+fn audit_line(timestamp: u64, args: &[String], code: i32) -> String {
+    let args = scrub_args(args)
+        .iter()
+        .map(|arg| {
+            if arg.is_empty() || arg.contains(char::is_whitespace) {
+                format!("{:?}", arg)
+            } else {
+                arg.clone()
+            }
+        })
+        .collect::<Vec<_>>();
+
+    format!("{} exit={} mz {}", timestamp, code, args.join(" "))
+}
+
+/// Appends the invocation, with the arguments following the program name,
+/// to the audit log.
+pub(crate) fn append_audit_log(path: &Path, args: &[String], code: i32) -> Result<()> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let mut log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .context("failed to open the audit log")?;
+
+    writeln!(log, "{}", audit_line(timestamp, args, code)).context("failed to write the audit log")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::audit_line;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_audit_line() {
+        let password = format!("mzp_{}", "1".repeat(64));
+        assert_eq!(
+            audit_line(
+                1_700_000_000,
+                &args(&["--profile", "staging", "login", "--interactive", &password]),
+                1
+            ),
+            "1700000000 exit=1 mz --profile staging login --interactive <redacted>"
+        );
+        assert_eq!(
+            audit_line(
+                1_700_000_000,
+                &args(&[
+                    "shell",
+                    "aws/us-east-1",
+                    "-c",
+                    "CREATE SECRET kafka AS 'hunter2'",
+                    "--set=application_name=ci",
+                    "--timing",
+                ]),
+                0
+            ),
+            "1700000000 exit=0 mz shell aws/us-east-1 -c <redacted> --set=<redacted> --timing"
+        );
+        assert_eq!(
+            audit_line(
+                1_700_000_000,
+                &args(&["login", "--client-id", "ci", "--secret", "hunter2"]),
+                0
+            ),
+            "1700000000 exit=0 mz login --client-id ci --secret <redacted>"
+        );
+        assert_eq!(
+            audit_line(
+                1_700_000_000,
+                &args(&[
+                    "shell",
+                    "aws/us-east-1",
+                    "-cCREATE SECRET kafka AS 'hunter2'",
+                ]),
+                0
+            ),
+            "1700000000 exit=0 mz shell aws/us-east-1 -c<redacted>"
+        );
+        assert_eq!(
+            audit_line(
+                1_700_000_000,
+                &args(&[
+                    "-v",
+                    "shell",
+                    "aws/us-east-1",
+                    "-vc",
+                    "ALTER SECRET kafka AS 'pw'"
+                ]),
+                0
+            ),
+            "1700000000 exit=0 mz -v shell aws/us-east-1 -vc <redacted>"
+        );
+        assert_eq!(
+            audit_line(
+                1_700_000_000,
+                &args(&["shell", "aws/us-east-1", "-vcSELECT 1", "-f", "setup.sql"]),
+                0
+            ),
+            "1700000000 exit=0 mz shell aws/us-east-1 -vc<redacted> -f setup.sql"
+        );
+        assert_eq!(
+            audit_line(1_700_000_000, &args(&["region", "list", ""]), 0),
+            "1700000000 exit=0 mz region list \"\""
+        );
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::future::Future;
+use std::time::Duration;
+
+use anyhow::Result;
+use reqwest::{Client, Url};
+
+/// Time to wait for an endpoint to respond.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Sends an unauthenticated request to the endpoint, returning the status of
+/// its response. Any response means the endpoint is reachable, and, if it is
+/// served over HTTPS, that its TLS certificate is valid.
+pub(crate) async fn probe_endpoint(client: &Client, url: &str) -> Result<u16> {
+    let response = client.head(url).timeout(CHECK_TIMEOUT).send().await?;
+
+    Ok(response.status().as_u16())
+}
+
+/// Whether the URL is served over HTTPS.
+fn is_https(url: &str) -> bool {
+    Url::parse(url).map_or(false, |url| url.scheme() == "https")
+}
+
+/// Probes every endpoint, in order.
+///
+/// Returns a line per endpoint, and the number of unreachable ones.
+pub(crate) async fn check_endpoints<'a, P, F>(
+    endpoints: &[(&str, &'a str)],
+    mut probe: P,
+) -> (Vec<String>, usize)
+where
+    P: FnMut(&'a str) -> F,
+    F: Future<Output = Result<u16>>,
+{
+    let mut lines = vec![];
+    let mut unreachable = 0;
+    for (name, url) in endpoints {
+        let status = match probe(url).await {
+            // An endpoint overridden with a plain HTTP URL has no TLS to verify.
+            Ok(status) if is_https(url) => format!("reachable (HTTP {}), TLS verified", status),
+            Ok(status) => format!("reachable (HTTP {})", status),
+            Err(error) => {
+                unreachable += 1;
+                format!("unreachable: {:#}", error)
+            }
+        };
+        lines.push(format!("{:<24} {}\n{:<24} {}", name, url, "", status));
+    }
+
+    (lines, unreachable)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::check_endpoints;
+
+    #[tokio::test]
+    async fn test_check_endpoints() {
+        let endpoints = [
+            ("cloud regions", "https://cloud.materialize.com/regions"),
+            ("user login", "https://admin.materialize.com/login"),
+            ("device login", "https://admin.materialize.com/device"),
+        ];
+        let (lines, unreachable) = check_endpoints(&endpoints, |url| async move {
+            match url {
+                "https://admin.materialize.com/login" => Err(anyhow!("connection refused")),
+                _ => Ok(405),
+            }
+        })
+        .await;
+
+        assert_eq!(unreachable, 1);
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].ends_with("reachable (HTTP 405), TLS verified"));
+        assert!(lines[1].starts_with("user login"));
+        assert!(lines[1].ends_with("unreachable: connection refused"));
+        assert!(lines[2].ends_with("reachable (HTTP 405), TLS verified"));
+
+        let (_, unreachable) = check_endpoints(&endpoints, |_| async { Ok(200) }).await;
+        assert_eq!(unreachable, 0);
+
+        // Only the endpoints served over HTTPS have their TLS verified.
+        let endpoints = [
+            ("cloud regions", "http://localhost:8080/regions"),
+            ("user login", "HTTPS://admin.materialize.com/login"),
+        ];
+        let (lines, _) = check_endpoints(&endpoints, |_| async { Ok(200) }).await;
+        assert!(lines[0].ends_with("reachable (HTTP 200)"));
+        assert!(lines[1].ends_with("reachable (HTTP 200), TLS verified"));
+    }
+}
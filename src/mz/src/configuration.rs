@@ -9,39 +9,212 @@
 
 use std::{
     collections::{BTreeMap, HashMap},
+    env,
     fmt::Display,
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Mutex,
 };
 
 use anyhow::{bail, Context, Ok, Result};
+use clap::ArgEnum;
 use dirs::home_dir;
 use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE, USER_AGENT};
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::endpoints::Endpoints;
+use crate::keyring::{keyring_account, keyring_reference, Keyring, SystemKeyring};
 use crate::region::CloudProviderRegion;
+use crate::token_cache::{self, TokenCache};
+use crate::utils::{send_with_retries, write_private, OutputFormat, SecretPolicy};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 struct Profile0 {
     email: String,
     #[serde(rename(serialize = "app-password", deserialize = "app-password"))]
     app_password: String,
     region: Option<CloudProviderRegion>,
+    #[serde(default, skip_serializing_if = "ProfileDefaults::is_empty")]
+    defaults: ProfileDefaults,
+}
+
+/// Values of the global options used with a profile,
+/// unless given on the command line.
+///
+/// E.g.:
+/// [profiles.default.defaults]
+/// format = "json"
+/// no-pager = true
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct ProfileDefaults {
+    pub(crate) format: Option<OutputFormat>,
+    pub(crate) no_pager: Option<bool>,
+}
+
+impl ProfileDefaults {
+    fn is_empty(&self) -> bool {
+        *self == ProfileDefaults::default()
+    }
+}
+
+/// Shell commands run after a command succeeds, with environment
+/// variables describing it. No hook runs unless configured.
+///
+/// E.g.:
+/// [hooks]
+/// on_enable = "notify-send \"$MZ_REGION is enabled\""
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Hooks {
+    /// Run after enabling a region
+    pub(crate) on_enable: Option<String>,
+    /// Also run the hooks after the command fails
+    #[serde(default)]
+    pub(crate) run_on_failure: bool,
+}
+
+impl Hooks {
+    fn is_empty(&self) -> bool {
+        *self == Hooks::default()
+    }
+}
+
+/// Values of a profile given through environment variables prefixed by
+/// the profile's name, which take precedence over the configuration file.
+///
+/// E.g.: for the `staging` profile
+/// MZ_STAGING_EMAIL, MZ_STAGING_APP_PASSWORD and MZ_STAGING_REGION
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ProfileOverrides {
+    email: Option<String>,
+    app_password: Option<String>,
+    region: Option<CloudProviderRegion>,
+}
+
+impl ProfileOverrides {
+    /// Reads the overrides of the profile through `var`, which returns
+    /// the value of an environment variable, if set.
+    fn read<F>(profile: &str, var: F) -> Result<ProfileOverrides>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let prefix = format!(
+            "MZ_{}_",
+            profile
+                .to_ascii_uppercase()
+                .replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+        );
+        let region = match var(&format!("{}REGION", prefix)) {
+            Some(region) => Some(
+                CloudProviderRegion::from_str(&region)
+                    .with_context(|| format!("invalid {}REGION", prefix))?,
+            ),
+            None => None,
+        };
+
+        Ok(ProfileOverrides {
+            email: var(&format!("{}EMAIL", prefix)),
+            app_password: var(&format!("{}APP_PASSWORD", prefix)),
+            region,
+        })
+    }
 }
 
+/// Name of the profile read from the standard input,
+/// e.g. `mz --profile - region list`.
+pub(crate) const STDIN_PROFILE: &str = "-";
+
+/// Error of a command run with a profile missing from the configuration file.
+pub(crate) const PROFILE_NOT_FOUND_MESSAGE: &str =
+    "Profile not found. Please, add one or login using `mz login`.";
+
 #[derive(Serialize, Deserialize, Debug)]
 pub(crate) struct Configuration {
     #[serde(skip)]
     modified: bool,
     current_profile: String,
     profiles: BTreeMap<String, Profile0>,
+    #[serde(default, skip_serializing_if = "Hooks::is_empty")]
+    hooks: Hooks,
+    /// Profiles defined only for the command, through environment variables
+    /// or on the standard input, which are never written out to the
+    /// configuration file.
+    #[serde(skip)]
+    ephemeral_profiles: BTreeMap<String, Profile0>,
+    /// Path the configuration is read from and written out to.
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+/// Profiles exported to, or imported from, a single bundle.
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct ProfilesBundle {
+    profiles: BTreeMap<String, Profile0>,
+}
+
+/// What to do when importing a profile that already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ArgEnum)]
+pub(crate) enum OnConflict {
+    Skip,
+    Overwrite,
+}
+
+/// A problem found in the configuration file by `mz profiles doctor`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ConfigProblem {
+    /// The file is not valid TOML, or does not describe profiles.
+    Malformed(String),
+    /// The profile is defined more than once.
+    DuplicateProfile(String),
+    /// The default profile does not exist.
+    DanglingDefault(String),
+    /// The app password of the profile is not a valid one.
+    InvalidAppPassword(String),
+}
+
+impl ConfigProblem {
+    /// Whether the problem can be repaired without losing any profile or secret.
+    pub(crate) fn is_fixable(&self) -> bool {
+        matches!(self, ConfigProblem::DanglingDefault(_))
+    }
+}
+
+impl Display for ConfigProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigProblem::Malformed(error) => {
+                write!(f, "the configuration file is malformed: {}", error)
+            }
+            ConfigProblem::DuplicateProfile(name) => {
+                write!(f, "profile {} is defined more than once", name)
+            }
+            ConfigProblem::DanglingDefault(name) => {
+                write!(f, "the default profile {} does not exist", name)
+            }
+            // Never include the app password itself.
+            ConfigProblem::InvalidAppPassword(name) => write!(
+                f,
+                "profile {} has an invalid app password. reauthorize using mz login",
+                name
+            ),
+        }
+    }
 }
 
 pub(crate) struct Profile<'a> {
     _modified: &'a mut bool,
     profile: &'a mut Profile0,
+    overrides: ProfileOverrides,
+    /// Name its access tokens are cached under, none for an ephemeral profile.
+    cache_key: Option<String>,
+    /// Path of the configuration file, next to which the access tokens are cached.
+    config_path: &'a Path,
+    /// The app password read from the keyring, if the configuration file
+    /// only keeps a reference to it.
+    keyring_app_password: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -59,7 +232,9 @@ pub(crate) struct FronteggAPIToken {
 
 pub(crate) struct ValidProfile<'a> {
     pub(crate) profile: &'a Profile<'a>,
-    pub(crate) frontegg_auth: FronteggAuth,
+    pub(crate) endpoints: &'a Endpoints,
+    /// The access token, and whether it was read from the token cache.
+    frontegg_auth: Mutex<(FronteggAuth, bool)>,
 }
 
 #[allow(dead_code)]
@@ -68,21 +243,10 @@ impl Configuration {
     const PROFILES_FILE_NAME: &str = "profiles.toml";
     const DEFAULT_PROFILE: &str = "default";
 
-    pub(crate) fn load() -> Result<Configuration> {
-        fn path_exists(path: &PathBuf) -> bool {
-            fs::metadata(path).is_ok()
-        }
-
-        let mut config_path = get_config_path()?;
-
-        if !path_exists(&config_path) {
-            fs::create_dir_all(config_path.as_path())
-                .context("failed to create directory for configuration file")?;
-        };
-
-        config_path.push(Self::PROFILES_FILE_NAME);
-
-        path_exists(&config_path)
+    /// Loads the configuration file at the path. A missing file is empty.
+    pub(crate) fn load(config_path: PathBuf) -> Result<Configuration> {
+        let config = fs::metadata(&config_path)
+            .is_ok()
             .then(|| {
                 let contents = fs::read_to_string(&config_path)
                     .context("failed to read configuration file")?;
@@ -90,28 +254,280 @@ impl Configuration {
                 let config = toml::from_str::<Configuration>(&contents).with_context(|| {
                     format!(
                         "failed to read profiles from configuration file {}",
-                        config_path.into_os_string().to_string_lossy()
+                        config_path.to_string_lossy()
                     )
                 })?;
 
                 Ok(config)
             })
-            .unwrap_or_else(|| Ok(Configuration::default()))
+            .unwrap_or_else(|| Ok(Configuration::default()))?;
+
+        Ok(Configuration {
+            path: config_path,
+            ..config
+        })
+    }
+
+    /// Returns the default path to the configuration file, in `$MZ_CONFIG_DIR`
+    /// when set, and in `~/.config/mz` otherwise.
+    pub(crate) fn default_path() -> Result<PathBuf> {
+        Self::default_path_with(env::var_os("MZ_CONFIG_DIR").map(PathBuf::from), home_dir())
+    }
+
+    fn default_path_with(
+        config_dir: Option<PathBuf>,
+        home_dir: Option<PathBuf>,
+    ) -> Result<PathBuf> {
+        let config_dir = match config_dir.filter(|dir| !dir.as_os_str().is_empty()) {
+            Some(config_dir) => config_dir,
+            None => home_dir
+                .context("failed to find $HOME directory")?
+                .join(Self::PROFILES_DIR_NAME),
+        };
+        Ok(config_dir.join(Self::PROFILES_FILE_NAME))
+    }
+
+    /// Returns the path to the configuration file.
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Scans the contents of a configuration file for problems.
+    pub(crate) fn diagnose(contents: &str) -> Vec<ConfigProblem> {
+        // Duplicated profiles make the whole file unreadable,
+        // so they are looked for in its table headers.
+        let mut names = BTreeMap::new();
+        for line in contents.lines() {
+            let header = line.trim();
+            let name = header
+                .strip_prefix("[profiles.")
+                .and_then(|header| header.strip_suffix(']'))
+                .map(str::trim);
+            match name {
+                Some(name) if name.starts_with('"') && name.ends_with('"') && name.len() > 1 => {
+                    *names
+                        .entry(name[1..name.len() - 1].to_string())
+                        .or_insert(0) += 1
+                }
+                Some(name) if !name.contains('.') => {
+                    *names.entry(name.to_string()).or_insert(0) += 1
+                }
+                _ => {}
+            }
+        }
+        let duplicates = names
+            .into_iter()
+            .filter(|(_, count)| *count > 1)
+            .map(|(name, _)| ConfigProblem::DuplicateProfile(name))
+            .collect::<Vec<_>>();
+        if !duplicates.is_empty() {
+            return duplicates;
+        }
+
+        let config = match toml::from_str::<Configuration>(contents) {
+            Err(error) => return vec![ConfigProblem::Malformed(error.to_string())],
+            result => result.expect("errors handled above"),
+        };
+
+        let mut problems = vec![];
+        if !config.profiles.is_empty() && !config.profiles.contains_key(&config.current_profile) {
+            problems.push(ConfigProblem::DanglingDefault(config.current_profile));
+        }
+        for (name, profile) in &config.profiles {
+            if keyring_account(&profile.app_password).is_none()
+                && FronteggAPIToken::try_from(profile.app_password.as_str()).is_err()
+            {
+                problems.push(ConfigProblem::InvalidAppPassword(name.clone()));
+            }
+        }
+
+        problems
+    }
+
+    /// Repairs the fixable problems of a configuration file,
+    /// returning the repaired contents if anything changed.
+    ///
+    /// A dangling default is pointed to the first profile.
+    /// The profiles themselves, and their app passwords, are kept as is.
+    pub(crate) fn repair(contents: &str) -> Result<Option<String>> {
+        let mut config = toml::from_str::<Configuration>(contents)
+            .context("failed to read profiles from configuration file")?;
+
+        match config.profiles.keys().next() {
+            Some(first) if !config.profiles.contains_key(&config.current_profile) => {
+                config.current_profile = first.clone();
+            }
+            _ => return Ok(None),
+        }
+
+        toml::to_string_pretty(&config)
+            .map(Some)
+            .context("failed to write out updated configuration")
     }
 
     pub(crate) fn current_profile(&self, profile: Option<String>) -> String {
         profile.unwrap_or_else(|| self.current_profile.clone())
     }
 
+    /// Returns the profile, reading its app password from the keyring if the
+    /// configuration file only keeps a reference to it.
     pub(crate) fn get_profile(&mut self, profile: Option<String>) -> Result<Profile> {
+        self.get_profile_with_env(profile, |key| env::var(key).ok())?
+            .with_keyring(&SystemKeyring)
+    }
+
+    /// Returns the profile, with the values of its environment variables, as
+    /// returned by `var`, taking precedence over the configuration file.
+    ///
+    /// A profile missing from the file may be defined entirely through its
+    /// environment variables, as long as they include its credentials.
+    fn get_profile_with_env<F>(&mut self, profile: Option<String>, var: F) -> Result<Profile<'_>>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
         let profile = self.current_profile(profile);
-        self.profiles
+        let overrides = ProfileOverrides::read(&profile, var)?;
+
+        let config_path = self.path.as_path();
+        let stored = self.profiles.contains_key(&profile) && profile != STDIN_PROFILE;
+        let cache_key = stored.then(|| profile.clone());
+        let profiles = if stored {
+            &mut self.profiles
+        } else {
+            if let (Some(email), Some(app_password)) = (&overrides.email, &overrides.app_password) {
+                self.ephemeral_profiles.insert(
+                    profile.clone(),
+                    Profile0 {
+                        email: email.clone(),
+                        app_password: app_password.clone(),
+                        region: None,
+                        defaults: Default::default(),
+                    },
+                );
+            }
+            &mut self.ephemeral_profiles
+        };
+
+        profiles
             .get_mut(&profile)
             .map(|p| Profile {
                 _modified: &mut self.modified,
                 profile: p,
+                overrides,
+                cache_key,
+                config_path,
+                keyring_app_password: None,
+            })
+            .context(PROFILE_NOT_FOUND_MESSAGE)
+    }
+
+    /// Adds the profile read from the standard input, a TOML or JSON document
+    /// with the fields of a profile in the configuration file, e.g.:
+    /// email = "ci@example.com"
+    /// app-password = "mzp_..."
+    ///
+    /// It is only used for the command, and never written out.
+    pub(crate) fn add_stdin_profile(&mut self, contents: &str) -> Result<()> {
+        let profile = if contents.trim_start().starts_with('{') {
+            serde_json::from_str::<Profile0>(contents)
+                .context("failed to read the profile from the standard input")?
+        } else {
+            toml::from_str::<Profile0>(contents)
+                .context("failed to read the profile from the standard input")?
+        };
+        FronteggAPIToken::try_from(profile.app_password.as_str())
+            .context("the profile on the standard input has an invalid app password")?;
+
+        self.ephemeral_profiles
+            .insert(STDIN_PROFILE.to_string(), profile);
+        Ok(())
+    }
+
+    /// Returns the defaults of the profile, none if the profile does not exist.
+    pub(crate) fn get_profile_defaults(&self, profile: Option<String>) -> ProfileDefaults {
+        let profile = self.current_profile(profile);
+        self.ephemeral_profiles
+            .get(STDIN_PROFILE)
+            .filter(|_| profile == STDIN_PROFILE)
+            .or_else(|| self.profiles.get(&profile))
+            .map(|p| p.defaults.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the hooks to run after the commands.
+    pub(crate) fn hooks(&self) -> &Hooks {
+        &self.hooks
+    }
+
+    /// Returns the names of the profiles stored in the configuration file:
+    /// all of them, or only the given one, if it is stored.
+    pub(crate) fn stored_profiles(&self, profile: Option<String>, all: bool) -> Vec<String> {
+        let profile = self.current_profile(profile);
+        self.profiles
+            .keys()
+            .filter(|name| all || **name == profile)
+            .cloned()
+            .collect()
+    }
+
+    /// Removes the profiles, and their app passwords, from the configuration file.
+    ///
+    /// Returns the keyring accounts of the app passwords no profile refers to anymore.
+    pub(crate) fn remove_profiles(&mut self, names: &[String]) -> Vec<String> {
+        let mut accounts = vec![];
+        for name in names {
+            if let Some(profile) = self.profiles.remove(name) {
+                self.modified = true;
+                accounts.extend(keyring_account(&profile.app_password).map(str::to_string));
+            }
+        }
+        // Profiles logged in with the same app password share its entry.
+        accounts.retain(|account| {
+            !self
+                .profiles
+                .values()
+                .any(|profile| keyring_account(&profile.app_password) == Some(account))
+        });
+        accounts.sort();
+        accounts.dedup();
+        accounts
+    }
+
+    /// Lists the profiles of the configuration file, a line each, with their
+    /// email, default region and app password, masked per the policy.
+    /// The default profile is marked as such.
+    pub(crate) fn list_profiles(&self, secret_policy: SecretPolicy) -> Vec<String> {
+        self.profiles
+            .iter()
+            .map(|(name, profile)| {
+                let name = if *name == self.current_profile {
+                    format!("{} (default)", name)
+                } else {
+                    name.clone()
+                };
+                let region = profile
+                    .region
+                    .map_or_else(|| "-".to_string(), |region| region.to_string());
+                format!(
+                    "{:<24} {:<32} {:<20} {}",
+                    name,
+                    profile.email,
+                    region,
+                    secret_policy.secret(&profile.app_password)
+                )
             })
-            .context("Profile not found. Please, add one or login using `mz login`.")
+            .collect()
+    }
+
+    /// Describes the profile and its default region compactly, for a shell prompt.
+    ///
+    /// E.g.: default:aws/us-east-1
+    pub(crate) fn prompt(&self, profile: Option<String>) -> String {
+        let profile = self.current_profile(profile);
+        match self.profiles.get(&profile).and_then(|p| p.region) {
+            Some(region) => format!("{}:{}", profile, region),
+            None => profile,
+        }
     }
 
     pub(crate) fn get_profiles(&self, profile: Option<String>) -> Vec<String> {
@@ -140,62 +556,176 @@ impl Configuration {
         api_token: FronteggAPIToken,
     ) {
         self.modified = true;
+        // Logging in again keeps the defaults of the profile.
+        let defaults = self
+            .profiles
+            .get(&name)
+            .map(|p| p.defaults.clone())
+            .unwrap_or_default();
         self.profiles.insert(
             name,
             Profile0 {
                 email,
                 app_password: api_token.to_string(),
                 region: None,
+                defaults,
             },
         );
     }
 
+    /// Moves the app password of the profile to the keyring, leaving
+    /// a reference to it in the configuration file.
+    pub(crate) fn move_to_keyring<K: Keyring>(&mut self, name: &str, keyring: &K) -> Result<()> {
+        let profile = self
+            .profiles
+            .get_mut(name)
+            .context(PROFILE_NOT_FOUND_MESSAGE)?;
+        if keyring_account(&profile.app_password).is_some() {
+            return Ok(());
+        }
+        let api_token = FronteggAPIToken::try_from(profile.app_password.as_str())?;
+        keyring
+            .set(&api_token.client_id, &profile.app_password)
+            .context("failed to store the app password in the keyring")?;
+
+        self.modified = true;
+        profile.app_password = keyring_reference(&api_token.client_id);
+        Ok(())
+    }
+
+    /// Exports a profile, or all the profiles if none is given, to a TOML bundle.
+    ///
+    /// The app passwords are masked unless the policy reveals them, in which
+    /// case those kept in the keyring are read from it.
+    pub(crate) fn export_profiles<K: Keyring>(
+        &self,
+        profile: Option<&str>,
+        secret_policy: SecretPolicy,
+        keyring: &K,
+    ) -> Result<String> {
+        let mut bundle = ProfilesBundle::default();
+        match profile {
+            Some(name) => {
+                let profile = self.profiles.get(name).context(PROFILE_NOT_FOUND_MESSAGE)?;
+                bundle.profiles.insert(name.to_string(), profile.clone());
+            }
+            None => bundle.profiles = self.profiles.clone(),
+        }
+
+        for (name, profile) in bundle.profiles.iter_mut() {
+            if let (Some(account), SecretPolicy::Reveal) =
+                (keyring_account(&profile.app_password), secret_policy)
+            {
+                profile.app_password = keyring.get(account).with_context(|| {
+                    format!(
+                        "failed to read the app password of {} from the keyring",
+                        name
+                    )
+                })?;
+            }
+            profile.app_password = secret_policy.secret(&profile.app_password).to_string();
+        }
+
+        toml::to_string_pretty(&bundle).context("failed to write out the profiles")
+    }
+
+    /// Imports the profiles of a TOML bundle, returning the names of the imported ones.
+    pub(crate) fn import_profiles(
+        &mut self,
+        contents: &str,
+        on_conflict: OnConflict,
+    ) -> Result<Vec<String>> {
+        let bundle =
+            toml::from_str::<ProfilesBundle>(contents).context("failed to read the profiles")?;
+
+        // Validate the whole bundle before importing anything.
+        for (name, profile) in &bundle.profiles {
+            FronteggAPIToken::try_from(profile.app_password.as_str()).with_context(|| {
+                format!("profile {} has an invalid or redacted app password", name)
+            })?;
+        }
+
+        let mut imported = vec![];
+        for (name, profile) in bundle.profiles {
+            if on_conflict == OnConflict::Skip && self.profiles.contains_key(&name) {
+                continue;
+            }
+            self.modified = true;
+            self.profiles.insert(name.clone(), profile);
+            imported.push(name);
+        }
+
+        Ok(imported)
+    }
+
     pub(crate) fn close(self) -> Result<()> {
         if !self.modified {
             return Ok(());
         }
 
-        let mut config_path = get_config_path()?;
-        config_path.push(Self::PROFILES_FILE_NAME);
+        if let Some(config_dir) = self.path.parent() {
+            fs::create_dir_all(config_dir)
+                .context("failed to create directory for configuration file")?;
+        }
 
         let contents =
             toml::to_string_pretty(&self).context("failed to write out updated configuration")?;
 
-        fs::write(config_path, contents).context("failed to write out updated configuration")
+        write_private(&self.path, &contents).context("failed to write out updated configuration")
     }
 }
 
-fn get_config_path() -> Result<PathBuf> {
-    home_dir()
-        .map(|mut path| {
-            path.push(Configuration::PROFILES_DIR_NAME);
-            path
-        })
-        .context("failed to find $HOME directory")
-}
-
 impl Default for Configuration {
     fn default() -> Self {
         Self {
             modified: false,
             current_profile: Self::DEFAULT_PROFILE.to_string(),
             profiles: Default::default(),
+            hooks: Default::default(),
+            ephemeral_profiles: Default::default(),
+            path: Default::default(),
         }
     }
 }
 
 #[allow(dead_code)]
 impl Profile<'_> {
+    pub(crate) fn config_path(&self) -> &Path {
+        self.config_path
+    }
+
     pub(crate) fn get_email(&self) -> &str {
-        &self.profile.email
+        self.overrides
+            .email
+            .as_deref()
+            .unwrap_or(&self.profile.email)
     }
 
     pub(crate) fn get_app_password(&self) -> &str {
-        &self.profile.app_password
+        self.overrides
+            .app_password
+            .as_deref()
+            .or(self.keyring_app_password.as_deref())
+            .unwrap_or(&self.profile.app_password)
+    }
+
+    /// Reads the app password from the keyring, if the configuration file
+    /// only keeps a reference to it and no environment variable overrides it.
+    fn with_keyring<K: Keyring>(mut self, keyring: &K) -> Result<Self> {
+        if self.overrides.app_password.is_some() {
+            return Ok(self);
+        }
+        if let Some(account) = keyring_account(&self.profile.app_password) {
+            let app_password = keyring
+                .get(account)
+                .context("failed to read the app password of the profile from the keyring")?;
+            self.keyring_app_password = Some(app_password);
+        }
+        Ok(self)
     }
 
     pub(crate) fn get_default_region(&self) -> Option<CloudProviderRegion> {
-        self.profile.region
+        self.overrides.region.or(self.profile.region)
     }
 
     pub(crate) fn set_email(&mut self, email: String) {
@@ -205,6 +735,7 @@ impl Profile<'_> {
 
     pub(crate) fn set_password(&mut self, password: String) {
         *self._modified = true;
+        self.keyring_app_password = None;
         self.profile.app_password = password;
     }
 
@@ -213,40 +744,109 @@ impl Profile<'_> {
         self.profile.region = Some(region)
     }
 
-    pub(crate) async fn validate(&self, client: &Client) -> Result<ValidProfile<'_>> {
-        let api_token: FronteggAPIToken = self.profile.app_password.as_str().try_into()?;
-        let mut access_token_request_body = HashMap::new();
-        access_token_request_body.insert("clientId", api_token.client_id.as_str());
-        access_token_request_body.insert("secret", api_token.secret.as_str());
+    /// Exchanges the app password for an access token, reusing the cached
+    /// one until it is about to expire.
+    pub(crate) async fn validate<'b>(
+        &'b self,
+        client: &Client,
+        endpoints: &'b Endpoints,
+    ) -> Result<ValidProfile<'b>> {
+        let api_token: FronteggAPIToken = self.get_app_password().try_into()?;
+        let cached = self.cache_key.as_ref().and_then(|key| {
+            TokenCache::load(self.config_path).get(key, &api_token.client_id, token_cache::now())
+        });
+        let frontegg_auth = match cached {
+            Some(auth) => (auth, true),
+            None => (self.authenticate(client, endpoints).await?, false),
+        };
 
-        let mut headers = HeaderMap::new();
-        headers.insert(USER_AGENT, HeaderValue::from_static("reqwest"));
-        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        Ok(ValidProfile {
+            profile: self,
+            endpoints,
+            frontegg_auth: Mutex::new(frontegg_auth),
+        })
+    }
 
-        let authentication_result = client
-            .post(crate::MACHINE_AUTH_URL)
-            .headers(headers)
-            .json(&access_token_request_body)
-            .send()
-            .await
-            .context("failed to connect to server")?;
+    /// Exchanges the app password for a new access token, and caches it.
+    pub(crate) async fn authenticate(
+        &self,
+        client: &Client,
+        endpoints: &Endpoints,
+    ) -> Result<FronteggAuth> {
+        let api_token: FronteggAPIToken = self.get_app_password().try_into()?;
+        let auth = authenticate_api_token(client, endpoints, &api_token).await?;
 
-        if authentication_result.status() == 401 {
-            bail!("Unauthorized. Please, check the credentials.");
+        // The cache only saves a round trip, so failing to write it is not an error.
+        if let Some(key) = &self.cache_key {
+            let mut cache = TokenCache::load(self.config_path);
+            cache.insert(key, &api_token.client_id, &auth, token_cache::now());
+            let _ = cache.save(self.config_path);
         }
 
-        let auth = authentication_result
-            .json::<FronteggAuth>()
-            .await
-            .context("failed to parse results from server")?;
+        Ok(auth)
+    }
+}
 
-        Ok(ValidProfile {
-            profile: self,
-            frontegg_auth: auth,
-        })
+impl ValidProfile<'_> {
+    pub(crate) fn frontegg_auth(&self) -> FronteggAuth {
+        self.frontegg_auth.lock().expect("lock poisoned").0.clone()
+    }
+
+    /// Sends the request built with the value of its authorization header.
+    ///
+    /// A cached access token rejected by the server is replaced with a new
+    /// one, and the request sent again.
+    pub(crate) async fn send<F>(&self, client: &Client, request: F) -> Result<Response>
+    where
+        F: Fn(&str) -> RequestBuilder,
+    {
+        let authorization = format!("Bearer {}", self.frontegg_auth().access_token);
+        let response = send_with_retries(|| request(&authorization)).await?;
+        let cached = self.frontegg_auth.lock().expect("lock poisoned").1;
+        if response.status() != StatusCode::UNAUTHORIZED || !cached {
+            return Ok(response);
+        }
+
+        let auth = self.profile.authenticate(client, self.endpoints).await?;
+        let authorization = format!("Bearer {}", auth.access_token);
+        *self.frontegg_auth.lock().expect("lock poisoned") = (auth, false);
+        send_with_retries(|| request(&authorization)).await
     }
 }
 
+/// Exchanges an app password for an access token.
+pub(crate) async fn authenticate_api_token(
+    client: &Client,
+    endpoints: &Endpoints,
+    api_token: &FronteggAPIToken,
+) -> Result<FronteggAuth> {
+    let mut access_token_request_body = HashMap::new();
+    access_token_request_body.insert("clientId", api_token.client_id.as_str());
+    access_token_request_body.insert("secret", api_token.secret.as_str());
+
+    let mut headers = HeaderMap::new();
+    headers.insert(USER_AGENT, HeaderValue::from_static("reqwest"));
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+    let authentication_result = send_with_retries(|| {
+        client
+            .post(&endpoints.machine_auth)
+            .headers(headers.clone())
+            .json(&access_token_request_body)
+    })
+    .await
+    .context("failed to connect to server")?;
+
+    if authentication_result.status() == 401 {
+        bail!("Unauthorized. Please, check the credentials.");
+    }
+
+    authentication_result
+        .json::<FronteggAuth>()
+        .await
+        .context("failed to parse results from server")
+}
+
 impl TryFrom<&str> for FronteggAPIToken {
     type Error = anyhow::Error;
 
@@ -274,3 +874,469 @@ impl Display for FronteggAPIToken {
         write!(f, "{}", parts)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use anyhow::{bail, Context, Result};
+
+    use super::{
+        ConfigProblem, Configuration, FronteggAPIToken, OnConflict, Profile0, STDIN_PROFILE,
+    };
+    use crate::keyring::Keyring;
+    use crate::region::CloudProviderRegion;
+    use crate::utils::SecretPolicy;
+
+    /// A keyring in memory, or no keyring at all if unavailable.
+    #[derive(Default)]
+    struct MemoryKeyring {
+        unavailable: bool,
+        secrets: RefCell<BTreeMap<String, String>>,
+    }
+
+    impl Keyring for MemoryKeyring {
+        fn get(&self, account: &str) -> Result<String> {
+            self.secrets
+                .borrow()
+                .get(account)
+                .cloned()
+                .context("not in the keyring")
+        }
+
+        fn set(&self, account: &str, secret: &str) -> Result<()> {
+            if self.unavailable {
+                bail!("no keyring available");
+            }
+            self.secrets
+                .borrow_mut()
+                .insert(account.to_string(), secret.to_string());
+            Ok(())
+        }
+
+        fn delete(&self, account: &str) -> Result<()> {
+            self.secrets.borrow_mut().remove(account);
+            Ok(())
+        }
+    }
+
+    fn profile(email: &str, app_password: &str) -> Profile0 {
+        Profile0 {
+            email: email.to_string(),
+            app_password: app_password.to_string(),
+            region: None,
+            defaults: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_default_path() {
+        let home = Some(PathBuf::from("/home/materialize"));
+        assert_eq!(
+            Configuration::default_path_with(None, home.clone()).unwrap(),
+            PathBuf::from("/home/materialize/.config/mz/profiles.toml")
+        );
+        assert_eq!(
+            Configuration::default_path_with(Some(PathBuf::from("/etc/mz")), home.clone()).unwrap(),
+            PathBuf::from("/etc/mz/profiles.toml")
+        );
+        assert_eq!(
+            Configuration::default_path_with(Some(PathBuf::new()), home).unwrap(),
+            PathBuf::from("/home/materialize/.config/mz/profiles.toml")
+        );
+        assert!(Configuration::default_path_with(None, None).is_err());
+    }
+
+    #[test]
+    fn test_load_close_path() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path().join("accounts");
+        let path = dir.join("ci.toml");
+
+        // A missing file is empty, and its directory is only created on write.
+        let mut config = Configuration::load(path.clone()).unwrap();
+        assert_eq!(config.path(), path);
+        assert!(config.profiles.is_empty());
+        assert!(!dir.exists());
+
+        let password = format!("mzp_{}", "1".repeat(64));
+        config
+            .profiles
+            .insert("ci".to_string(), profile("ci@materialize.com", &password));
+        config.modified = true;
+        config.close().unwrap();
+
+        let config = Configuration::load(path.clone()).unwrap();
+        assert_eq!(config.profiles["ci"].email, "ci@materialize.com");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_close_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("profiles.toml");
+        // A file left world-readable by an earlier version is replaced.
+        fs::write(&path, "current_profile = \"default\"\n[profiles]\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let mut config = Configuration::load(path.clone()).unwrap();
+        let api_token = FronteggAPIToken::try_from(format!("mzp_{}", "1".repeat(64)).as_str());
+        config.create_or_update_profile(
+            "default".to_string(),
+            "ci@materialize.com".to_string(),
+            api_token.unwrap(),
+        );
+        config.close().unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+        let config = Configuration::load(path).unwrap();
+        assert_eq!(config.profiles["default"].email, "ci@materialize.com");
+    }
+
+    #[test]
+    fn test_export_import_profiles() {
+        let first_password = format!("mzp_{}", "1".repeat(64));
+        let second_password = format!("mzp_{}", "2".repeat(64));
+
+        let mut config = Configuration::default();
+        config.profiles.insert(
+            "default".to_string(),
+            profile("default@materialize.com", &first_password),
+        );
+        config.profiles.insert(
+            "staging".to_string(),
+            profile("staging@materialize.com", &second_password),
+        );
+        let bundle = config
+            .export_profiles(None, SecretPolicy::Reveal, &MemoryKeyring::default())
+            .unwrap();
+
+        let mut other = Configuration::default();
+        other.profiles.insert(
+            "default".to_string(),
+            profile("other@materialize.com", &second_password),
+        );
+        let imported = other.import_profiles(&bundle, OnConflict::Skip).unwrap();
+        assert_eq!(imported, vec!["staging".to_string()]);
+        assert_eq!(other.profiles["default"].email, "other@materialize.com");
+        assert_eq!(other.profiles["staging"].email, "staging@materialize.com");
+
+        let imported = other
+            .import_profiles(&bundle, OnConflict::Overwrite)
+            .unwrap();
+        assert_eq!(imported, vec!["default".to_string(), "staging".to_string()]);
+        assert_eq!(other.profiles["default"].email, "default@materialize.com");
+        assert_eq!(other.profiles["default"].app_password, first_password);
+    }
+
+    #[test]
+    fn test_import_redacted_profiles() {
+        let mut config = Configuration::default();
+        config.profiles.insert(
+            "default".to_string(),
+            profile(
+                "default@materialize.com",
+                &format!("mzp_{}", "1".repeat(64)),
+            ),
+        );
+        let bundle = config
+            .export_profiles(
+                Some("default"),
+                SecretPolicy::Mask,
+                &MemoryKeyring::default(),
+            )
+            .unwrap();
+        assert!(bundle.contains("<redacted>"));
+
+        let mut other = Configuration::default();
+        assert!(other
+            .import_profiles(&bundle, OnConflict::Overwrite)
+            .is_err());
+        assert!(other.profiles.is_empty());
+    }
+
+    #[test]
+    fn test_keyring() {
+        let password = format!("mzp_{}", "1".repeat(64));
+        let mut config = Configuration::default();
+        config.profiles.insert(
+            "default".to_string(),
+            profile("ci@materialize.com", &password),
+        );
+        config.profiles.insert(
+            "staging".to_string(),
+            profile("ci@materialize.com", &password),
+        );
+
+        // Without a keyring, the app password stays in the configuration file.
+        let unavailable = MemoryKeyring {
+            unavailable: true,
+            ..Default::default()
+        };
+        assert!(config.move_to_keyring("default", &unavailable).is_err());
+        assert_eq!(config.profiles["default"].app_password, password);
+
+        let keyring = MemoryKeyring::default();
+        config.move_to_keyring("default", &keyring).unwrap();
+        config.move_to_keyring("staging", &keyring).unwrap();
+        let contents = toml::to_string_pretty(&config).unwrap();
+        assert!(!contents.contains(&password));
+        assert!(contents.contains("app-password = 'keyring:11111111-1111-1111-1111-111111111111'"));
+        assert_eq!(keyring.secrets.borrow().len(), 1);
+        assert!(Configuration::diagnose(&contents).is_empty());
+
+        let profile = config
+            .get_profile_with_env(None, |_| None)
+            .and_then(|profile| profile.with_keyring(&keyring))
+            .unwrap();
+        assert_eq!(profile.get_app_password(), password);
+        assert!(config
+            .get_profile_with_env(None, |_| None)
+            .and_then(|profile| profile.with_keyring(&MemoryKeyring::default()))
+            .is_err());
+
+        let bundle = config
+            .export_profiles(Some("default"), SecretPolicy::Reveal, &keyring)
+            .unwrap();
+        assert!(bundle.contains(&password));
+
+        // The app password is only removed from the keyring with its last profile.
+        assert!(config.remove_profiles(&["default".to_string()]).is_empty());
+        assert_eq!(
+            config.remove_profiles(&["staging".to_string()]),
+            vec!["11111111-1111-1111-1111-111111111111".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_prompt() {
+        let mut config = Configuration::default();
+        let mut default = profile("default@materialize.com", "");
+        default.region = Some(CloudProviderRegion::AwsUsEast1);
+        config.profiles.insert("default".to_string(), default);
+        config.profiles.insert(
+            "staging".to_string(),
+            profile("staging@materialize.com", ""),
+        );
+
+        assert_eq!(config.prompt(None), "default:aws/us-east-1");
+        assert_eq!(config.prompt(Some("staging".to_string())), "staging");
+        assert_eq!(config.prompt(Some("missing".to_string())), "missing");
+    }
+
+    #[test]
+    fn test_logout_profiles() {
+        let mut config = Configuration::default();
+        assert!(config.stored_profiles(None, true).is_empty());
+        for name in ["default", "staging", "test"] {
+            config.profiles.insert(
+                name.to_string(),
+                profile(&format!("{}@materialize.com", name), ""),
+            );
+        }
+
+        assert_eq!(config.stored_profiles(None, false), vec!["default"]);
+        assert_eq!(
+            config.stored_profiles(Some("staging".to_string()), false),
+            vec!["staging"]
+        );
+        assert!(config
+            .stored_profiles(Some("missing".to_string()), false)
+            .is_empty());
+        assert_eq!(
+            config.stored_profiles(Some("staging".to_string()), true),
+            vec!["default", "staging", "test"]
+        );
+
+        config.remove_profiles(&[]);
+        assert!(!config.modified);
+        config.remove_profiles(&["staging".to_string(), "missing".to_string()]);
+        assert!(config.modified);
+        assert_eq!(config.stored_profiles(None, true), vec!["default", "test"]);
+    }
+
+    #[test]
+    fn test_list_profiles() {
+        let password = format!("mzp_{}", "1".repeat(64));
+        let mut config = Configuration::default();
+        assert!(config.list_profiles(SecretPolicy::Mask).is_empty());
+
+        let mut default = profile("default@materialize.com", &password);
+        default.region = Some(CloudProviderRegion::AwsUsEast1);
+        config.profiles.insert("default".to_string(), default);
+        config.profiles.insert(
+            "staging".to_string(),
+            profile("staging@materialize.com", &password),
+        );
+
+        let lines = config.list_profiles(SecretPolicy::Mask);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("default (default) "));
+        assert!(lines[0].contains("default@materialize.com"));
+        assert!(lines[0].contains("aws/us-east-1"));
+        assert!(lines[0].ends_with("<redacted>"));
+        assert!(lines[1].starts_with("staging "));
+        assert!(!lines[1].contains("(default)"));
+        assert!(lines.iter().all(|line| !line.contains(&password)));
+
+        config.update_default_profile("staging".to_string());
+        let lines = config.list_profiles(SecretPolicy::Reveal);
+        assert!(lines[1].starts_with("staging (default) "));
+        assert!(lines[1].ends_with(&password));
+    }
+
+    #[test]
+    fn test_diagnose_dangling_default() {
+        let password = format!("mzp_{}", "1".repeat(64));
+        let contents = format!(
+            "current_profile = \"removed\"\n\n\
+             [profiles.staging]\nemail = \"staging@materialize.com\"\napp-password = \"{}\"\n\n\
+             [profiles.test]\nemail = \"test@materialize.com\"\napp-password = \"invalid\"\n",
+            password
+        );
+
+        let problems = Configuration::diagnose(&contents);
+        assert_eq!(
+            problems,
+            vec![
+                ConfigProblem::DanglingDefault("removed".to_string()),
+                ConfigProblem::InvalidAppPassword("test".to_string()),
+            ]
+        );
+        assert!(problems[0].is_fixable());
+        assert!(!problems[1].is_fixable());
+        assert!(!problems[1].to_string().contains("invalid\""));
+
+        let repaired = Configuration::repair(&contents).unwrap().unwrap();
+        assert_eq!(
+            Configuration::diagnose(&repaired),
+            vec![ConfigProblem::InvalidAppPassword("test".to_string())]
+        );
+        let repaired_config = toml::from_str::<Configuration>(&repaired).unwrap();
+        assert_eq!(repaired_config.current_profile, "staging");
+        assert_eq!(repaired_config.profiles["staging"].app_password, password);
+        assert_eq!(Configuration::repair(&repaired).unwrap(), None);
+    }
+
+    #[test]
+    fn test_diagnose_duplicate_profile() {
+        let contents = "current_profile = \"default\"\n\n\
+             [profiles.default]\nemail = \"default@materialize.com\"\n\n\
+             [profiles.default.defaults]\nformat = \"json\"\n\n\
+             [profiles.\"default\"]\nemail = \"other@materialize.com\"\n";
+
+        let problems = Configuration::diagnose(contents);
+        assert_eq!(
+            problems,
+            vec![ConfigProblem::DuplicateProfile("default".to_string())]
+        );
+        assert!(!problems[0].is_fixable());
+        assert!(Configuration::repair(contents).is_err());
+
+        assert!(matches!(
+            Configuration::diagnose("current_profile = ")[..],
+            [ConfigProblem::Malformed(_)]
+        ));
+    }
+
+    #[test]
+    fn test_profile_env_overrides() {
+        let mut config = Configuration::default();
+        config.profiles.insert(
+            "default".to_string(),
+            profile("default@materialize.com", "file-default"),
+        );
+        config.profiles.insert(
+            "staging".to_string(),
+            profile("staging@materialize.com", "file-staging"),
+        );
+        let env = |key: &str| match key {
+            "MZ_STAGING_APP_PASSWORD" => Some("env-staging".to_string()),
+            "MZ_STAGING_REGION" => Some("aws/eu-west-1".to_string()),
+            "MZ_CI_RUNNER_EMAIL" => Some("ci@materialize.com".to_string()),
+            "MZ_CI_RUNNER_APP_PASSWORD" => Some("env-ci".to_string()),
+            _ => None,
+        };
+
+        let staging = config
+            .get_profile_with_env(Some("staging".to_string()), env)
+            .unwrap();
+        assert_eq!(staging.get_app_password(), "env-staging");
+        assert_eq!(staging.get_email(), "staging@materialize.com");
+        assert_eq!(
+            staging.get_default_region(),
+            Some(CloudProviderRegion::AwsEuWest1)
+        );
+
+        let default = config.get_profile_with_env(None, env).unwrap();
+        assert_eq!(default.get_app_password(), "file-default");
+        assert_eq!(default.get_default_region(), None);
+
+        let ci = config
+            .get_profile_with_env(Some("ci-runner".to_string()), env)
+            .unwrap();
+        assert_eq!(ci.get_email(), "ci@materialize.com");
+        assert_eq!(ci.get_app_password(), "env-ci");
+
+        // The overrides are never written out to the configuration file.
+        assert_eq!(config.profiles["staging"].app_password, "file-staging");
+        assert!(!config.profiles.contains_key("ci-runner"));
+        assert!(config
+            .get_profile_with_env(Some("missing".to_string()), env)
+            .is_err());
+    }
+
+    #[test]
+    fn test_stdin_profile() {
+        let app_password = format!("mzp_{}", "1".repeat(64));
+        let mut config = Configuration::default();
+        config.profiles.insert(
+            "default".to_string(),
+            profile("default@materialize.com", "file-default"),
+        );
+        config
+            .add_stdin_profile(&format!(
+                "email = \"ci@materialize.com\"\napp-password = \"{}\"\nregion = \"aws/eu-west-1\"\n",
+                app_password
+            ))
+            .unwrap();
+
+        let stdin = config
+            .get_profile_with_env(Some(STDIN_PROFILE.to_string()), |_| None)
+            .unwrap();
+        assert_eq!(stdin.get_email(), "ci@materialize.com");
+        assert_eq!(stdin.get_app_password(), app_password);
+        assert_eq!(
+            stdin.get_default_region(),
+            Some(CloudProviderRegion::AwsEuWest1)
+        );
+
+        // The profile is never written out to the configuration file.
+        let contents = toml::to_string_pretty(&config).unwrap();
+        assert!(!contents.contains("ci@materialize.com"));
+        assert!(!contents.contains(&app_password));
+
+        config
+            .add_stdin_profile(&format!(
+                r#"{{"email": "json@materialize.com", "app-password": "{}"}}"#,
+                app_password
+            ))
+            .unwrap();
+        let stdin = config
+            .get_profile_with_env(Some(STDIN_PROFILE.to_string()), |_| None)
+            .unwrap();
+        assert_eq!(stdin.get_email(), "json@materialize.com");
+
+        assert!(config
+            .add_stdin_profile("email = \"ci@materialize.com\"\napp-password = \"<redacted>\"\n")
+            .is_err());
+        assert!(config.add_stdin_profile("").is_err());
+    }
+}
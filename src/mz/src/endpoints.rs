@@ -0,0 +1,114 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::env;
+
+const CLOUD_PROVIDERS_URL: &str = "https://cloud.materialize.com/_metadata/cloud-regions.json";
+const API_TOKEN_AUTH_URL: &str =
+    "https://admin.cloud.materialize.com/identity/resources/users/api-tokens/v1";
+const API_FRONTEGG_TOKEN_AUTH_URL: &str =
+    "https://admin.cloud.materialize.com/frontegg/identity/resources/users/api-tokens/v1";
+const USER_AUTH_URL: &str =
+    "https://admin.cloud.materialize.com/frontegg/identity/resources/auth/v1/user";
+const MACHINE_AUTH_URL: &str =
+    "https://admin.cloud.materialize.com/identity/resources/auth/v1/api-token";
+const DEVICE_AUTH_URL: &str =
+    "https://admin.cloud.materialize.com/identity/resources/auth/v1/device";
+const WEB_LOGIN_URL: &str = "https://cloud.materialize.com/account/login?redirectUrl=/access/cli";
+
+/// URLs of the Materialize Cloud API.
+///
+/// Each one defaults to production, and is overridden by its environment
+/// variable, e.g. `MZ_CLOUD_PROVIDERS_URL`, when set to a non-empty value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Endpoints {
+    pub(crate) cloud_providers: String,
+    pub(crate) api_token_auth: String,
+    pub(crate) frontegg_api_token_auth: String,
+    pub(crate) user_auth: String,
+    pub(crate) machine_auth: String,
+    pub(crate) device_auth: String,
+    pub(crate) web_login: String,
+}
+
+impl Endpoints {
+    /// Reads the endpoints from the environment.
+    pub(crate) fn from_env() -> Endpoints {
+        Endpoints::from_env_with(|name| env::var(name).ok())
+    }
+
+    fn from_env_with<F>(var: F) -> Endpoints
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let endpoint = |name: &str, default: &str| {
+            var(name)
+                .filter(|url| !url.is_empty())
+                .unwrap_or_else(|| default.to_string())
+        };
+        Endpoints {
+            cloud_providers: endpoint("MZ_CLOUD_PROVIDERS_URL", CLOUD_PROVIDERS_URL),
+            api_token_auth: endpoint("MZ_API_TOKEN_AUTH_URL", API_TOKEN_AUTH_URL),
+            frontegg_api_token_auth: endpoint(
+                "MZ_API_FRONTEGG_TOKEN_AUTH_URL",
+                API_FRONTEGG_TOKEN_AUTH_URL,
+            ),
+            user_auth: endpoint("MZ_USER_AUTH_URL", USER_AUTH_URL),
+            machine_auth: endpoint("MZ_MACHINE_AUTH_URL", MACHINE_AUTH_URL),
+            device_auth: endpoint("MZ_DEVICE_AUTH_URL", DEVICE_AUTH_URL),
+            web_login: endpoint("MZ_WEB_LOGIN_URL", WEB_LOGIN_URL),
+        }
+    }
+
+    /// Lists the endpoints, by name.
+    pub(crate) fn list(&self) -> [(&'static str, &str); 7] {
+        [
+            ("cloud regions", &self.cloud_providers),
+            ("app passwords", &self.api_token_auth),
+            ("frontegg app passwords", &self.frontegg_api_token_auth),
+            ("user login", &self.user_auth),
+            ("app password login", &self.machine_auth),
+            ("device login", &self.device_auth),
+            ("web login", &self.web_login),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Endpoints, CLOUD_PROVIDERS_URL, USER_AUTH_URL};
+
+    #[test]
+    fn test_endpoints_from_env() {
+        let endpoints = Endpoints::from_env_with(|name| match name {
+            "MZ_CLOUD_PROVIDERS_URL" => Some("https://staging.example.com/regions".to_string()),
+            "MZ_MACHINE_AUTH_URL" => Some("http://localhost:8080/auth".to_string()),
+            "MZ_USER_AUTH_URL" => Some("".to_string()),
+            _ => None,
+        });
+        assert_eq!(
+            endpoints.cloud_providers,
+            "https://staging.example.com/regions"
+        );
+        assert_eq!(endpoints.machine_auth, "http://localhost:8080/auth");
+        assert_eq!(endpoints.user_auth, USER_AUTH_URL);
+
+        let defaults = Endpoints::from_env_with(|_| None);
+        assert_eq!(defaults.cloud_providers, CLOUD_PROVIDERS_URL);
+        assert_ne!(endpoints, defaults);
+        assert_eq!(
+            Endpoints {
+                cloud_providers: CLOUD_PROVIDERS_URL.to_string(),
+                machine_auth: defaults.machine_auth.clone(),
+                ..endpoints
+            },
+            defaults
+        );
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::process::Command;
+
+use anyhow::{ensure, Context, Result};
+
+use crate::configuration::Hooks;
+use crate::region::CloudProviderRegion;
+
+/// Runs the hook through the shell, with the environment variables set.
+fn run_hook(name: &str, hook: &str, env: &[(&str, &str)]) -> Result<()> {
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(hook)
+        .envs(env.iter().copied())
+        .status()
+        .with_context(|| format!("failed to run the {} hook", name))?;
+    ensure!(status.success(), "the {} hook failed ({})", name, status);
+
+    Ok(())
+}
+
+/// Runs the `on_enable` hook, if there is one, after enabling the region.
+///
+/// The hook only runs after a failed enable if `run_on_failure` is set.
+pub(crate) fn run_enable_hook<T>(
+    hooks: &Hooks,
+    profile: &str,
+    cloud_provider_region: CloudProviderRegion,
+    result: &Result<T>,
+) -> Result<()> {
+    let hook = match &hooks.on_enable {
+        Some(hook) if result.is_ok() || hooks.run_on_failure => hook,
+        _ => return Ok(()),
+    };
+    let status = if result.is_ok() { "success" } else { "failure" };
+
+    run_hook(
+        "on_enable",
+        hook,
+        &[
+            ("MZ_HOOK_STATUS", status),
+            ("MZ_PROFILE", profile),
+            ("MZ_CLOUD_PROVIDER", cloud_provider_region.provider_name()),
+            ("MZ_REGION", cloud_provider_region.region_name()),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use anyhow::{anyhow, Result};
+    use tempfile::NamedTempFile;
+
+    use super::run_enable_hook;
+    use crate::configuration::Hooks;
+    use crate::region::CloudProviderRegion;
+
+    #[test]
+    fn test_run_enable_hook() {
+        let file = NamedTempFile::new().unwrap();
+        let path = file.path();
+        let hooks = toml::from_str::<Hooks>(&format!(
+            r#"on_enable = 'echo "$MZ_PROFILE $MZ_CLOUD_PROVIDER $MZ_REGION $MZ_HOOK_STATUS" >> {}'"#,
+            path.display()
+        ))
+        .unwrap();
+        let enabled: Result<()> = Ok(());
+        let failed: Result<()> = Err(anyhow!("the region is not healthy"));
+
+        run_enable_hook(&hooks, "staging", CloudProviderRegion::AwsEuWest1, &enabled).unwrap();
+        // Hooks only run after a failure when asked to.
+        run_enable_hook(&hooks, "staging", CloudProviderRegion::AwsUsEast1, &failed).unwrap();
+        run_enable_hook(
+            &Hooks {
+                run_on_failure: true,
+                ..hooks.clone()
+            },
+            "default",
+            CloudProviderRegion::AwsUsEast1,
+            &failed,
+        )
+        .unwrap();
+        let contents = fs::read_to_string(path).unwrap();
+        assert_eq!(
+            contents,
+            "staging aws eu-west-1 success\ndefault aws us-east-1 failure\n"
+        );
+
+        run_enable_hook(
+            &Hooks::default(),
+            "default",
+            CloudProviderRegion::AwsUsEast1,
+            &enabled,
+        )
+        .unwrap();
+        let failing = Hooks {
+            on_enable: Some("exit 3".to_string()),
+            run_on_failure: false,
+        };
+        let error = run_enable_hook(
+            &failing,
+            "default",
+            CloudProviderRegion::AwsUsEast1,
+            &enabled,
+        )
+        .unwrap_err();
+        assert!(error.to_string().starts_with("the on_enable hook failed"));
+    }
+}
@@ -0,0 +1,79 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! App passwords kept in the keyring of the operating system, instead of in
+//! plaintext in the configuration file, which only keeps a reference to them.
+//!
+//! The keyring is the login keychain on macOS, the Credential Manager on
+//! Windows, and the Secret Service, e.g. GNOME Keyring, elsewhere.
+
+use std::env;
+
+use anyhow::{Context, Result};
+use keyring::Entry;
+
+/// Environment variable storing the app passwords in the keyring when
+/// logging in, as with `--keyring`, when set to a non-empty value.
+const KEYRING_VAR: &str = "MZ_USE_KEYRING";
+
+/// Service the app passwords are stored under in the keyring.
+const SERVICE: &str = "materialize-mz";
+
+/// Prefix of the reference to an app password kept in the keyring, followed
+/// by the account it is stored under, e.g. `keyring:<client ID>`.
+const REFERENCE_PREFIX: &str = "keyring:";
+
+/// Whether to store the app passwords in the keyring when logging in.
+pub(crate) fn use_keyring(keyring: bool) -> bool {
+    keyring || env::var_os(KEYRING_VAR).map_or(false, |value| !value.is_empty())
+}
+
+/// Returns the account of the app password kept in the keyring,
+/// if the app password is a reference to one.
+pub(crate) fn keyring_account(app_password: &str) -> Option<&str> {
+    app_password.strip_prefix(REFERENCE_PREFIX)
+}
+
+/// Returns the reference to the app password stored under the account.
+pub(crate) fn keyring_reference(account: &str) -> String {
+    format!("{}{}", REFERENCE_PREFIX, account)
+}
+
+/// Storage of secrets by account
+pub(crate) trait Keyring {
+    /// Reads the secret, failing if there is no keyring available
+    /// or the secret is not in it
+    fn get(&self, account: &str) -> Result<String>;
+    /// Stores the secret, replacing the one of the account, if any
+    fn set(&self, account: &str, secret: &str) -> Result<()>;
+    /// Removes the secret of the account
+    fn delete(&self, account: &str) -> Result<()>;
+}
+
+/// The keyring of the operating system
+pub(crate) struct SystemKeyring;
+
+/// Returns the entry of the account in the keyring.
+fn entry(account: &str) -> Result<Entry> {
+    Entry::new(SERVICE, account).context("no keyring available")
+}
+
+impl Keyring for SystemKeyring {
+    fn get(&self, account: &str) -> Result<String> {
+        Ok(entry(account)?.get_password()?)
+    }
+
+    fn set(&self, account: &str, secret: &str) -> Result<()> {
+        Ok(entry(account)?.set_password(secret)?)
+    }
+
+    fn delete(&self, account: &str) -> Result<()> {
+        Ok(entry(account)?.delete_password()?)
+    }
+}
@@ -10,14 +10,22 @@
 use anyhow::{bail, Context, Ok, Result};
 use axum::http::StatusCode;
 use axum::{extract::Query, response::IntoResponse, routing::get, Router};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
 use std::net::SocketAddr;
+use std::time::{Duration, Instant};
 
 use std::{collections::HashMap, io::Write};
 use tokio::sync::broadcast::{channel, Sender};
 
-use crate::configuration::{Configuration, FronteggAPIToken, FronteggAuth};
-use crate::utils::trim_newline;
-use crate::{BrowserAPIToken, API_TOKEN_AUTH_URL, USER_AUTH_URL, WEB_LOGIN_URL};
+use crate::configuration::{
+    authenticate_api_token, Configuration, FronteggAPIToken, FronteggAuth, Profile,
+};
+use crate::endpoints::Endpoints;
+use crate::region::CloudProviderRegion;
+use crate::token_cache::token_claims;
+use crate::utils::{send_with_retries, trim_newline, OutputFormat};
+use crate::BrowserAPIToken;
 
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
 use reqwest::Client;
@@ -45,11 +53,12 @@ async fn request(
 
 /// Log the user using the browser, generates an API token and saves the new profile data.
 pub(crate) async fn login_with_browser(
+    endpoints: &Endpoints,
     profile_name: &str,
     config: &mut Configuration,
 ) -> Result<()> {
     // Open the browser to login user
-    let path = format!("{:}?profile_name={:}", WEB_LOGIN_URL, profile_name);
+    let path = format!("{:}?profile_name={:}", endpoints.web_login, profile_name);
     if let Err(err) = open::that(path.clone()) {
         bail!("An error occurred when opening '{}': {}", path, err)
     }
@@ -84,9 +93,10 @@ pub(crate) async fn login_with_browser(
 /// Generates an API token using an access token
 pub(crate) async fn generate_api_token(
     client: &Client,
+    endpoints: &Endpoints,
     access_token_response: FronteggAuth,
     description: &String,
-) -> Result<FronteggAPIToken, reqwest::Error> {
+) -> Result<FronteggAPIToken> {
     let authorization: String = format!("Bearer {}", access_token_response.access_token);
 
     let mut headers = HeaderMap::new();
@@ -99,18 +109,24 @@ pub(crate) async fn generate_api_token(
     let mut body = HashMap::new();
     body.insert("description", description);
 
-    client
-        .post(API_TOKEN_AUTH_URL)
-        .headers(headers)
-        .json(&body)
-        .send()
-        .await?
-        .json::<FronteggAPIToken>()
-        .await
+    Ok(send_with_retries(|| {
+        client
+            .post(&endpoints.api_token_auth)
+            .headers(headers.clone())
+            .json(&body)
+    })
+    .await?
+    .json::<FronteggAPIToken>()
+    .await?)
 }
 
 /// Generates an access token using an API token
-async fn authenticate_user(client: &Client, email: &str, password: &str) -> Result<FronteggAuth> {
+async fn authenticate_user(
+    client: &Client,
+    endpoints: &Endpoints,
+    email: &str,
+    password: &str,
+) -> Result<FronteggAuth> {
     let mut access_token_request_body = HashMap::new();
     access_token_request_body.insert("email", email);
     access_token_request_body.insert("password", password);
@@ -119,12 +135,13 @@ async fn authenticate_user(client: &Client, email: &str, password: &str) -> Resu
     headers.insert(USER_AGENT, HeaderValue::from_static("reqwest"));
     headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
 
-    let response = client
-        .post(USER_AUTH_URL)
-        .headers(headers)
-        .json(&access_token_request_body)
-        .send()
-        .await?;
+    let response = send_with_retries(|| {
+        client
+            .post(&endpoints.user_auth)
+            .headers(headers.clone())
+            .json(&access_token_request_body)
+    })
+    .await?;
 
     match response.status() {
         StatusCode::UNAUTHORIZED => {
@@ -139,6 +156,8 @@ async fn authenticate_user(client: &Client, email: &str, password: &str) -> Resu
 
 /// Log the user using the console, generates an API token and saves the new profile data.
 pub(crate) async fn login_with_console(
+    client: &Client,
+    endpoints: &Endpoints,
     profile_name: &String,
     config: &mut Configuration,
 ) -> Result<()> {
@@ -154,13 +173,12 @@ pub(crate) async fn login_with_console(
     let _ = std::io::stdout().flush();
     let password = rpassword::read_password().unwrap();
 
-    let client = Client::new();
-
     // Check if there is a secret somewhere.
     // If there is none save the api token someone on the root folder.
-    let auth_user = authenticate_user(&client, &email, &password).await?;
+    let auth_user = authenticate_user(client, endpoints, &email, &password).await?;
     let api_token = generate_api_token(
-        &client,
+        client,
+        endpoints,
         auth_user,
         &String::from("App password for the CLI"),
     )
@@ -170,3 +188,356 @@ pub(crate) async fn login_with_console(
 
     Ok(())
 }
+
+/// Error of a login with a client ID and secret that do not authenticate.
+pub(crate) const ERROR_AUTHENTICATING_PROFILE_MESSAGE: &str =
+    "failed to authenticate with the client ID and secret. check them, or login using mz login";
+
+/// Reads the email of the user the access token was issued for.
+fn token_email(auth: &FronteggAuth) -> Option<String> {
+    #[derive(Deserialize)]
+    struct Claims {
+        email: String,
+    }
+
+    token_claims::<Claims>(&auth.access_token).map(|claims| claims.email)
+}
+
+/// Returns the email of the profile, as passed or read from the access token.
+fn credentials_email(email: Option<String>, auth: &FronteggAuth) -> Result<String> {
+    email
+        .or_else(|| token_email(auth))
+        .context("the access token does not include an email. Pass it with --email")
+}
+
+/// Characters of the client ID printed by `mz whoami`, enough to tell the
+/// app passwords apart without printing the whole of it.
+const CLIENT_ID_PREFIX_LEN: usize = 8;
+
+/// Identity a profile authenticates as, as printed by `mz whoami`.
+#[derive(Debug, Serialize)]
+pub(crate) struct Identity {
+    profile: String,
+    email: String,
+    /// The client ID of the app password, truncated.
+    client_id: String,
+    region: Option<String>,
+}
+
+impl Identity {
+    fn new(
+        profile: String,
+        email: String,
+        client_id: &str,
+        region: Option<CloudProviderRegion>,
+    ) -> Identity {
+        let client_id = match client_id.char_indices().nth(CLIENT_ID_PREFIX_LEN) {
+            Some((end, _)) => format!("{}...", &client_id[..end]),
+            None => client_id.to_string(),
+        };
+        Identity {
+            profile,
+            email,
+            client_id,
+            region: region.map(|region| region.to_string()),
+        }
+    }
+
+    /// Describes the identity with a line per field.
+    pub(crate) fn describe(&self, format: OutputFormat) -> Result<String> {
+        Ok(match format {
+            OutputFormat::Text => format!(
+                "profile:   {}\nemail:     {}\nclient id: {}\nregion:    {}",
+                self.profile,
+                self.email,
+                self.client_id,
+                self.region.as_deref().unwrap_or("-")
+            ),
+            OutputFormat::Json => serde_json::to_string(self)?,
+        })
+    }
+}
+
+/// Authenticates the profile with its app password, never with a cached
+/// access token, and returns the identity it authenticates as.
+pub(crate) async fn whoami(
+    client: &Client,
+    endpoints: &Endpoints,
+    profile_name: String,
+    profile: &Profile<'_>,
+) -> Result<Identity> {
+    let api_token: FronteggAPIToken = profile.get_app_password().try_into()?;
+    let auth = profile
+        .authenticate(client, endpoints)
+        .await
+        .with_context(|| {
+            format!(
+                "the credentials of profile {} are invalid. reauthorize using mz login",
+                profile_name
+            )
+        })?;
+    // The token is authoritative, the profile's email is only what was typed at login.
+    let email = token_email(&auth).unwrap_or_else(|| profile.get_email().to_string());
+    Ok(Identity::new(
+        profile_name,
+        email,
+        &api_token.client_id,
+        profile.get_default_region(),
+    ))
+}
+
+/// Log the user using the client ID and secret of an app password, e.g. in CI,
+/// and saves the new profile data once they authenticate.
+pub(crate) async fn login_with_credentials(
+    client: &Client,
+    endpoints: &Endpoints,
+    profile_name: &str,
+    config: &mut Configuration,
+    client_id: &str,
+    secret: &str,
+    email: Option<String>,
+) -> Result<()> {
+    let app_password = format!("mzp_{}{}", client_id, secret).replace('-', "");
+    let api_token =
+        FronteggAPIToken::try_from(app_password.as_str()).context("invalid client ID or secret")?;
+    let auth = authenticate_api_token(client, endpoints, &api_token)
+        .await
+        .context(ERROR_AUTHENTICATING_PROFILE_MESSAGE)?;
+    let email = credentials_email(email, &auth)?;
+
+    config.create_or_update_profile(profile_name.to_string(), email, api_token);
+
+    Ok(())
+}
+
+/// Device code issued to login from another device
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeviceCode {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    /// Seconds until the device code expires
+    expires_in: u64,
+    /// Seconds to wait between polls
+    interval: u64,
+}
+
+/// State of a device code authorization
+#[derive(Debug)]
+enum DeviceAuthorization {
+    Pending,
+    Authorized(BrowserAPIToken),
+    Expired,
+}
+
+/// Requests a device code from the control plane
+async fn request_device_code(client: &Client, endpoints: &Endpoints) -> Result<DeviceCode> {
+    let mut headers = HeaderMap::new();
+    headers.insert(USER_AGENT, HeaderValue::from_static("reqwest"));
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+    send_with_retries(|| client.post(&endpoints.device_auth).headers(headers.clone()))
+        .await
+        .context("failed to connect to server")?
+        .error_for_status()
+        .context("failed to request a device code")?
+        .json::<DeviceCode>()
+        .await
+        .context("failed to parse response from server")
+}
+
+/// Checks whether the user authorized the device code
+async fn check_device_authorization(
+    client: &Client,
+    endpoints: &Endpoints,
+    device_code: &str,
+) -> Result<DeviceAuthorization> {
+    let mut headers = HeaderMap::new();
+    headers.insert(USER_AGENT, HeaderValue::from_static("reqwest"));
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    let mut body = HashMap::new();
+    body.insert("deviceCode", device_code);
+
+    let response = send_with_retries(|| {
+        client
+            .post(&endpoints.machine_auth)
+            .headers(headers.clone())
+            .json(&body)
+    })
+    .await
+    .context("failed to connect to server")?;
+
+    match response.status() {
+        StatusCode::PRECONDITION_REQUIRED => Ok(DeviceAuthorization::Pending),
+        StatusCode::GONE => Ok(DeviceAuthorization::Expired),
+        _ => Ok(DeviceAuthorization::Authorized(
+            response
+                .error_for_status()
+                .context("failed to check the device authorization")?
+                .json::<BrowserAPIToken>()
+                .await
+                .context("failed to parse response from server")?,
+        )),
+    }
+}
+
+/// Polls the authorization until the user authorizes it or it expires.
+async fn poll_device_authorization<F, Fut>(
+    mut check: F,
+    interval: Duration,
+    expires_in: Duration,
+) -> Result<BrowserAPIToken>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<DeviceAuthorization>>,
+{
+    let start = Instant::now();
+
+    loop {
+        match check().await? {
+            DeviceAuthorization::Authorized(api_token) => return Ok(api_token),
+            DeviceAuthorization::Expired => break,
+            DeviceAuthorization::Pending if start.elapsed() >= expires_in => break,
+            DeviceAuthorization::Pending => tokio::time::sleep(interval).await,
+        }
+    }
+
+    bail!("the device code expired before being authorized. Please, login again.")
+}
+
+/// Log the user from another device, using a device code, and saves the new profile data.
+pub(crate) async fn login_with_device(
+    client: &Client,
+    endpoints: &Endpoints,
+    profile_name: &str,
+    config: &mut Configuration,
+) -> Result<()> {
+    let device_code = request_device_code(client, endpoints).await?;
+
+    println!(
+        "Open {} on any device and enter the code: {}",
+        device_code.verification_uri, device_code.user_code
+    );
+
+    let BrowserAPIToken {
+        email,
+        client_id,
+        secret,
+    } = poll_device_authorization(
+        || check_device_authorization(client, endpoints, &device_code.device_code),
+        Duration::from_secs(device_code.interval),
+        Duration::from_secs(device_code.expires_in),
+    )
+    .await?;
+
+    config.create_or_update_profile(
+        profile_name.to_string(),
+        email,
+        FronteggAPIToken { client_id, secret },
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use openssl::base64::encode_block;
+
+    use serde_json::Value;
+
+    use super::{credentials_email, poll_device_authorization, DeviceAuthorization, Identity};
+    use crate::configuration::FronteggAuth;
+    use crate::region::CloudProviderRegion;
+    use crate::utils::OutputFormat;
+    use crate::BrowserAPIToken;
+
+    #[test]
+    fn test_credentials_email() {
+        let payload = encode_block(br#"{"email":"ci@example.com","exp":1700000000}"#);
+        let auth = FronteggAuth {
+            access_token: format!("eyJhbGciOiJSUzI1NiJ9.{}.c2lnbmF0dXJl", payload),
+        };
+        assert_eq!(credentials_email(None, &auth).unwrap(), "ci@example.com");
+        assert_eq!(
+            credentials_email(Some("bot@example.com".to_string()), &auth).unwrap(),
+            "bot@example.com"
+        );
+
+        let opaque = FronteggAuth {
+            access_token: "opaque".to_string(),
+        };
+        assert!(credentials_email(None, &opaque).is_err());
+    }
+
+    #[test]
+    fn test_identity() {
+        let identity = Identity::new(
+            "default".to_string(),
+            "ci@example.com".to_string(),
+            "0123abcd-4567-89ef-0123-456789abcdef",
+            Some(CloudProviderRegion::AwsUsEast1),
+        );
+        assert_eq!(
+            identity.describe(OutputFormat::Text).unwrap(),
+            "profile:   default\nemail:     ci@example.com\nclient id: 0123abcd...\nregion:    aws/us-east-1"
+        );
+        let json: Value =
+            serde_json::from_str(&identity.describe(OutputFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["client_id"], "0123abcd...");
+        assert_eq!(json["region"], "aws/us-east-1");
+
+        let identity = Identity::new("ci".to_string(), "ci@example.com".to_string(), "0123", None);
+        assert!(identity
+            .describe(OutputFormat::Text)
+            .unwrap()
+            .ends_with("client id: 0123\nregion:    -"));
+        let json: Value =
+            serde_json::from_str(&identity.describe(OutputFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["region"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_poll_device_authorization() {
+        let mut checks = 0;
+        let api_token = poll_device_authorization(
+            || {
+                checks += 1;
+                let authorization = if checks < 3 {
+                    DeviceAuthorization::Pending
+                } else {
+                    DeviceAuthorization::Authorized(BrowserAPIToken {
+                        email: "materialize@example.com".to_string(),
+                        client_id: "client".to_string(),
+                        secret: "secret".to_string(),
+                    })
+                };
+                async move { Ok(authorization) }
+            },
+            Duration::ZERO,
+            Duration::from_secs(60),
+        )
+        .await
+        .unwrap();
+        assert_eq!(checks, 3);
+        assert_eq!(api_token.email, "materialize@example.com");
+
+        let result = poll_device_authorization(
+            || async { Ok(DeviceAuthorization::Expired) },
+            Duration::ZERO,
+            Duration::from_secs(60),
+        )
+        .await;
+        assert!(result.is_err());
+
+        let result = poll_device_authorization(
+            || async { Ok(DeviceAuthorization::Pending) },
+            Duration::ZERO,
+            Duration::ZERO,
+        )
+        .await;
+        assert!(result.is_err());
+    }
+}
@@ -15,35 +15,67 @@
 
 extern crate core;
 
+mod audit_log;
+mod check;
+mod completions;
 mod configuration;
+mod endpoints;
+mod hooks;
+mod keyring;
 mod login;
 mod password;
 mod region;
 mod shell;
+mod token_cache;
 mod utils;
+mod version;
 
+use std::cell::RefCell;
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::{Duration, Instant};
 
-use anyhow::{Context, Result};
-use configuration::Configuration;
+use anyhow::{ensure, Context, Result};
+use audit_log::{append_audit_log, AUDIT_LOG_VAR};
+use check::{check_endpoints, probe_endpoint};
+use completions::CompletionShell;
+use configuration::{Configuration, OnConflict, ProfileDefaults, STDIN_PROFILE};
+use endpoints::Endpoints;
+use hooks::run_enable_hook;
 use login::generate_api_token;
-use password::list_passwords;
+use password::{list_passwords, revoke_password};
 use region::{
+    format_environment_status, format_region_enabled, get_certificate_expiry_days,
     get_provider_by_region_name, get_provider_region_environment, get_region_environment,
-    print_environment_status, print_region_enabled,
+    list_regions_json, print_environment_status, watch, HealthCache, HEALTH_CACHE_TTL,
+    HEALTH_POLL_INTERVAL, WATCH_INTERVAL_SECS,
 };
 use serde::Deserialize;
 
-use clap::{Args, Parser, Subcommand};
-use reqwest::Client;
-use shell::check_environment_health;
-use utils::run_loading_spinner;
+use clap::{ArgAction, Args, CommandFactory, ErrorKind, Parser, Subcommand};
+use clap_complete::Shell;
+use shell::{check_environment_health, parse_query_parameter, parse_session_variable};
+use token_cache::TokenCache;
+use utils::{
+    ctrl_c, exit_code, exit_with_fail_message, finish_loading_spinner, http_client, print_paged,
+    run_loading_spinner, set_verbosity, with_deadline, with_interrupt, write_private, OutputFormat,
+    RegionUnhealthy, SecretPolicy, INSECURE_WARNING,
+};
+use version::VERSION;
 
-use crate::login::{login_with_browser, login_with_console};
+use crate::keyring::{use_keyring, Keyring, SystemKeyring};
+use crate::login::{
+    login_with_browser, login_with_console, login_with_credentials, login_with_device, whoami,
+};
 use crate::region::{
-    enable_region_environment, list_cloud_providers, list_regions, CloudProviderRegion,
+    delete_region_environment, enable_region_environment, format_connection_details,
+    list_cloud_providers, list_regions, setup_or_rollback, wait_until_healthy,
+    warning_delete_region, CloudProviderRegion, ListOutput, RegionConfig,
 };
-use crate::shell::shell;
+use crate::shell::{connection_string, shell, ConnectionStringStyle, CopyFormat, PsqlSettings};
 
 /// Command-line interface for Materialize.
 #[derive(Debug, Parser)]
@@ -51,16 +83,47 @@ use crate::shell::shell;
 #[clap(about = "Command-line interface for Materialize.", long_about = None)]
 struct Cli {
     #[clap(subcommand)]
-    command: Commands,
-    /// Identify using a particular profile
-    #[clap(short, long, env = "MZ_PROFILE", default_value = "default")]
-    profile: String,
+    command: Option<Commands>,
+    /// Print the version, with the build metadata
+    #[clap(short = 'V', long = "version")]
+    print_version: bool,
+    /// Identify using a particular profile, or `-` to read one from the standard input [env: MZ_PROFILE] [default: default]
+    #[clap(short, long)]
+    profile: Option<String>,
+    /// Output format [default: text]
+    #[clap(long, arg_enum, global = true)]
+    format: Option<OutputFormat>,
+    /// Never page long listings
+    #[clap(long, global = true)]
+    no_pager: bool,
+    /// Print secrets, such as app passwords and tokens, instead of masking them
+    #[clap(long, global = true)]
+    reveal_secrets: bool,
+    /// Abort the command if it runs longer than the number of seconds
+    #[clap(long, global = true, value_name = "SECONDS")]
+    deadline: Option<u64>,
+    /// Accept invalid TLS certificates, e.g. of a self-signed staging endpoint
+    #[clap(long, global = true)]
+    insecure: bool,
+    /// Read and write the profiles in this file instead of the default one
+    #[clap(long, global = true, value_name = "PATH")]
+    profiles_file: Option<PathBuf>,
+    /// Log the HTTP requests and the statuses of their responses, and with -vv their bodies
+    #[clap(short, long, global = true, action = ArgAction::Count)]
+    verbose: u8,
 }
 
 #[derive(Debug, Subcommand)]
 enum Commands {
     /// Show commands to interact with passwords
     AppPassword(AppPasswordCommand),
+    /// Check that every endpoint the CLI uses is reachable
+    Check,
+    /// Print the completion script of a shell
+    Completions {
+        #[clap(arg_enum)]
+        shell: CompletionShell,
+    },
     /// Open the docs
     Docs,
     /// Open the web login
@@ -68,6 +131,46 @@ enum Commands {
         /// Login by typing your email and password
         #[clap(short, long)]
         interactive: bool,
+        /// Login with the client ID of an app password, e.g. in CI, along with --secret
+        #[clap(
+            long,
+            env = "MZ_CLIENT_ID",
+            requires = "secret",
+            hide_env_values = true
+        )]
+        client_id: Option<String>,
+        /// Secret of the app password to login with
+        #[clap(
+            long,
+            env = "MZ_CLIENT_SECRET",
+            requires = "client-id",
+            hide_env_values = true
+        )]
+        secret: Option<String>,
+        /// Email of the app password's user, read from its access token by default
+        #[clap(long, requires = "client-id")]
+        email: Option<String>,
+        /// Store the app password in the keyring of the operating system, as with MZ_USE_KEYRING=1
+        #[clap(long)]
+        keyring: bool,
+        #[clap(subcommand)]
+        command: Option<LoginCommand>,
+    },
+    /// Remove the profile, or all of them, from the configuration file
+    Logout {
+        /// Remove all the profiles
+        #[clap(long)]
+        all: bool,
+        /// Also revoke the app passwords, so they can no longer be used
+        #[clap(long)]
+        revoke: bool,
+    },
+    /// Print the profile and its region, for use in a shell prompt
+    Prompt,
+    /// Show commands to export and import profiles
+    Profiles {
+        #[clap(subcommand)]
+        command: ProfilesCommand,
     },
     /// Show commands to interact with regions
     Region {
@@ -78,7 +181,76 @@ enum Commands {
     Shell {
         #[clap(possible_values = CloudProviderRegion::variants())]
         cloud_provider_region: String,
+        /// Print the table, view and source names starting with a prefix
+        #[clap(long, value_name = "PREFIX")]
+        completion: Option<String>,
+        /// Cancel the statements running longer than the number of seconds
+        #[clap(long, value_name = "SECONDS")]
+        statement_timeout: Option<u64>,
+        /// Print how long each statement took
+        #[clap(long)]
+        timing: bool,
+        /// Fetch the results of queries in batches of this many rows
+        #[clap(long, value_name = "ROWS")]
+        fetch_size: Option<u64>,
+        /// Append the statements and their results to a file
+        #[clap(long, value_name = "FILE")]
+        transcript: Option<PathBuf>,
+        /// Run a statement or a describe meta-command, e.g. `\dt`, and exit
+        #[clap(short, long, value_name = "COMMAND")]
+        command: Option<String>,
+        /// Run the statements of a SQL script, and exit. Stops at the first failing one
+        #[clap(short, long, value_name = "FILE", conflicts_with = "command")]
+        file: Option<PathBuf>,
+        /// Set a session variable on connect. Can be repeated
+        #[clap(long = "set", value_name = "KEY=VALUE", parse(try_from_str = parse_session_variable))]
+        variables: Vec<(String, String)>,
+        /// Bind a value to the `$N` placeholder of the command. Can be repeated
+        #[clap(long = "param", value_name = "N=VALUE", requires = "command", parse(try_from_str = parse_query_parameter))]
+        parameters: Vec<(usize, String)>,
+        /// Copy the result of the command to the clipboard instead of printing it
+        #[clap(long, arg_enum, value_name = "FORMAT", requires = "command")]
+        copy: Option<CopyFormat>,
+    },
+    /// Print the version, with the Git SHA and the date of the build
+    Version,
+    /// Show the email, app password and region the profile authenticates as
+    Whoami,
+}
+
+#[derive(Debug, Subcommand)]
+enum ProfilesCommand {
+    /// List the profiles, with their email and default region.
+    List,
+    /// Export the profile, or all of them, to a TOML bundle.
+    Export {
+        /// Export all the profiles
+        #[clap(long)]
+        all: bool,
+        /// Write the bundle to a file instead of stdout
+        #[clap(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Import all the profiles of a TOML bundle.
+    Import {
+        /// Read the bundle from a file instead of stdin
+        file: Option<PathBuf>,
+        /// What to do with the profiles that already exist
+        #[clap(long, arg_enum, default_value = "skip")]
+        on_conflict: OnConflict,
     },
+    /// Detect, and optionally repair, problems in the configuration file.
+    Doctor {
+        /// Repair the problems that are safe to repair
+        #[clap(long)]
+        fix: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum LoginCommand {
+    /// Login from another device, by entering a code in its browser.
+    Device,
 }
 
 #[derive(Debug, Args)]
@@ -102,15 +274,67 @@ enum AppPasswordSubommand {
 enum RegionCommand {
     /// Enable a region.
     Enable {
+        #[clap(
+            possible_values = CloudProviderRegion::variants(),
+            required_unless_present = "from-file"
+        )]
+        cloud_provider_region: Option<String>,
+        /// Read the region to enable from a TOML file, e.g. `region = "aws/us-east-1"`.
+        #[clap(long, value_name = "FILE", conflicts_with = "cloud-provider-region")]
+        from_file: Option<PathBuf>,
+        /// Print the request and the response of the enable call, with their secrets masked.
+        #[clap(long)]
+        debug_http: bool,
+        /// Keep the region if its setup fails after it was created, instead of deleting it.
+        #[clap(long)]
+        no_rollback: bool,
+        /// Wait for the region to be healthy even if it was already enabled. A new region is always waited on
+        #[clap(long)]
+        wait: bool,
+        /// Fail if the region is not healthy after this many seconds of waiting
+        #[clap(long, value_name = "SECONDS")]
+        timeout: Option<u64>,
+    },
+    /// Delete a region, and all of its data.
+    Delete {
+        #[clap(possible_values = CloudProviderRegion::variants())]
+        cloud_provider_region: String,
+        /// Delete the region without asking to confirm
+        #[clap(short, long)]
+        yes: bool,
+    },
+    /// Print the string connecting to a region, with the profile's credentials.
+    ConnectionString {
         #[clap(possible_values = CloudProviderRegion::variants())]
         cloud_provider_region: String,
+        /// Print a URI, shell exports of the libpq environment variables, or a psql command
+        #[clap(long, arg_enum, default_value = "uri")]
+        style: ConnectionStringStyle,
     },
     /// List all enabled regions.
-    List,
+    List {
+        /// Print the regions as a table or as JSON, instead of in the --format
+        #[clap(short, long, arg_enum)]
+        output: Option<ListOutput>,
+    },
     /// Display a region's status.
     Status {
         #[clap(possible_values = CloudProviderRegion::variants())]
         cloud_provider_region: String,
+        /// Warn if the TLS certificate expires within this many days.
+        #[clap(long, value_name = "DAYS")]
+        cert_warn_days: Option<u32>,
+    },
+    /// Watch a region's status until interrupted.
+    Watch {
+        #[clap(possible_values = CloudProviderRegion::variants())]
+        cloud_provider_region: String,
+        /// Seconds between each refresh.
+        #[clap(long, value_name = "SECONDS", default_value_t = WATCH_INTERVAL_SECS)]
+        interval: u64,
+        /// Probe the region's health on every refresh, instead of reusing a recent result.
+        #[clap(long)]
+        no_cache: bool,
     },
 }
 
@@ -151,44 +375,204 @@ struct BrowserAPIToken {
     secret: String,
 }
 
+#[derive(Debug)]
 struct CloudProviderAndRegion {
     cloud_provider: CloudProvider,
     region: Option<Region>,
 }
 
 /// Constants
-const CLOUD_PROVIDERS_URL: &str = "https://cloud.materialize.com/_metadata/cloud-regions.json";
-const API_TOKEN_AUTH_URL: &str =
-    "https://admin.cloud.materialize.com/identity/resources/users/api-tokens/v1";
-const API_FRONTEGG_TOKEN_AUTH_URL: &str =
-    "https://admin.cloud.materialize.com/frontegg/identity/resources/users/api-tokens/v1";
-const USER_AUTH_URL: &str =
-    "https://admin.cloud.materialize.com/frontegg/identity/resources/auth/v1/user";
-const MACHINE_AUTH_URL: &str =
-    "https://admin.cloud.materialize.com/identity/resources/auth/v1/api-token";
-const WEB_LOGIN_URL: &str = "https://cloud.materialize.com/account/login?redirectUrl=/access/cli";
 const WEB_DOCS_URL: &str = "https://www.materialize.com/docs";
+const PROFILE_VAR: &str = "MZ_PROFILE";
+const DEFAULT_PROFILE: &str = "default";
+
+impl Cli {
+    /// Returns the name of the profile to use.
+    fn profile(&self) -> String {
+        self.profile_with_env(|name| env::var(name).ok())
+    }
+
+    /// The flag takes precedence over `MZ_PROFILE`, which takes precedence
+    /// over the default profile.
+    fn profile_with_env<F>(&self, var: F) -> String
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        self.profile
+            .clone()
+            .or_else(|| var(PROFILE_VAR).filter(|profile| !profile.is_empty()))
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+    }
+}
+
+impl Commands {
+    /// Whether the command reads the standard input, e.g. to confirm it or
+    /// to run an interactive shell, so that it cannot read the profile too.
+    fn reads_stdin(&self) -> bool {
+        match self {
+            Commands::Profiles {
+                command: ProfilesCommand::Import { file, .. },
+            } => file.is_none(),
+            Commands::Region {
+                command: RegionCommand::Delete { yes, .. },
+            } => !yes,
+            Commands::Shell {
+                completion,
+                command,
+                file,
+                ..
+            } => match file {
+                Some(file) => file == Path::new("-"),
+                None => completion.is_none() && command.is_none(),
+            },
+            _ => false,
+        }
+    }
+}
+
+/// Global options, as given on the command line or else as
+/// the defaults of the profile.
+#[derive(Debug)]
+struct GlobalOptions {
+    format: OutputFormat,
+    no_pager: bool,
+}
+
+impl GlobalOptions {
+    fn new(args: &Cli, defaults: &ProfileDefaults) -> GlobalOptions {
+        GlobalOptions {
+            format: args
+                .format
+                .or(defaults.format)
+                .unwrap_or(OutputFormat::Text),
+            no_pager: args.no_pager || defaults.no_pager.unwrap_or(false),
+        }
+    }
+}
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    let args = Cli::parse();
-    let profile_name = args.profile;
-    let mut config = Configuration::load()?;
-    match args.command {
+async fn main() {
+    let mut args = Cli::parse();
+    let secret_policy = SecretPolicy::new(args.reveal_secrets);
+    if args.insecure {
+        eprintln!("{}", INSECURE_WARNING);
+    }
+    set_verbosity(args.verbose);
+
+    let mut format = args.format.unwrap_or(OutputFormat::Text);
+
+    if args.print_version {
+        match VERSION.format(format) {
+            Ok(version) => println!("{}", version),
+            Err(error) => exit_with_fail_message(format, secret_policy, &error),
+        }
+        return;
+    }
+    let command = match args.command.take() {
+        Some(command) => command,
+        None => Cli::command()
+            .error(ErrorKind::MissingSubcommand, "a subcommand is required")
+            .exit(),
+    };
+
+    // The doctor reads the configuration file itself,
+    // as it must run even when the file cannot be loaded.
+    let config_path = match args.profiles_file.clone() {
+        Some(path) => Ok(path),
+        None => Configuration::default_path(),
+    };
+    let result = if let Commands::Profiles {
+        command: ProfilesCommand::Doctor { fix },
+    } = command
+    {
+        config_path.and_then(|path| profiles_doctor(&path, fix))
+    } else {
+        match config_path.and_then(|path| load_configuration(path, &args.profile(), &command)) {
+            Ok(config) => {
+                let options =
+                    GlobalOptions::new(&args, &config.get_profile_defaults(Some(args.profile())));
+                format = options.format;
+                let deadline = args.deadline.map(Duration::from_secs);
+                // psql handles Ctrl-C itself, cancelling the running statement.
+                let interrupt = (!matches!(command, Commands::Shell { .. })).then(ctrl_c);
+                let command = with_interrupt(interrupt, run(command, args, config, &options));
+                with_deadline(deadline, command).await
+            }
+            Err(error) => Err(error),
+        }
+    };
+
+    // Every command that returns is logged, including the interactive shell,
+    // as psql runs as a child process. Only a second Ctrl-C, which exits
+    // right away, skips the log.
+    if let Some(path) = env::var_os(AUDIT_LOG_VAR) {
+        let code = result.as_ref().map_or_else(exit_code, |_| 0);
+        let args = env::args().skip(1).collect::<Vec<_>>();
+        if let Err(error) = append_audit_log(path.as_ref(), &args, code) {
+            eprintln!("Warning: {:#}", error);
+        }
+    }
+
+    if let Err(error) = result {
+        exit_with_fail_message(format, secret_policy, &error);
+    }
+}
+
+/// Loads the configuration, with the profile read from the standard input
+/// when the profile is `-`.
+fn load_configuration(
+    config_path: PathBuf,
+    profile: &str,
+    command: &Commands,
+) -> Result<Configuration> {
+    let mut config = Configuration::load(config_path)?;
+    if profile == STDIN_PROFILE {
+        // The whole standard input is read up front, leaving nothing to the
+        // command, e.g. an empty bundle to import.
+        ensure!(
+            !command.reads_stdin(),
+            "cannot read the profile from the standard input, as the command reads it too"
+        );
+        let mut contents = String::new();
+        std::io::stdin()
+            .read_to_string(&mut contents)
+            .context("failed to read the profile from the standard input")?;
+        config.add_stdin_profile(&contents)?;
+    }
+    Ok(config)
+}
+
+async fn run(
+    command: Commands,
+    args: Cli,
+    mut config: Configuration,
+    options: &GlobalOptions,
+) -> Result<()> {
+    let profile_name = args.profile();
+    let no_pager = options.no_pager;
+    let secret_policy = SecretPolicy::new(args.reveal_secrets);
+    let insecure = args.insecure;
+    let endpoints = Endpoints::from_env();
+    match command {
         Commands::AppPassword(password_cmd) => {
             let profile = config.get_profile(Some(profile_name))?;
 
-            let client = Client::new();
+            let client = http_client(insecure)?;
             let valid_profile = profile
-                .validate(&client)
+                .validate(&client, &endpoints)
                 .await
                 .context("failed to validate profile. reauthorize using mz login")?;
 
             match password_cmd.command {
                 AppPasswordSubommand::Create { name } => {
-                    let api_token = generate_api_token(&client, valid_profile.frontegg_auth, &name)
-                        .await
-                        .with_context(|| "failed to create a new app password")?;
+                    let api_token = generate_api_token(
+                        &client,
+                        &endpoints,
+                        valid_profile.frontegg_auth(),
+                        &name,
+                    )
+                    .await
+                    .with_context(|| "failed to create a new app password")?;
 
                     println!("{}", api_token)
                 }
@@ -214,36 +598,343 @@ async fn main() -> Result<()> {
             }
         }
 
+        Commands::Completions { shell } => clap_complete::generate(
+            Shell::from(shell),
+            &mut Cli::command(),
+            "mz",
+            &mut std::io::stdout(),
+        ),
+
+        Commands::Check => {
+            ensure!(
+                !insecure,
+                "mz check verifies the TLS certificates, it cannot be used with --insecure"
+            );
+            // No credentials are needed, so this works before logging in.
+            let client = http_client(insecure)?;
+            let endpoints = endpoints.list();
+            let (lines, unreachable) =
+                check_endpoints(&endpoints, |url| probe_endpoint(&client, url)).await;
+            println!("{}", lines.join("\n"));
+            ensure!(
+                unreachable == 0,
+                "{} of {} endpoints are unreachable",
+                unreachable,
+                endpoints.len()
+            );
+        }
+
         Commands::Docs => {
             // Open the browser docs
             open::that(WEB_DOCS_URL).with_context(|| "Opening the browser.")?
         }
 
-        Commands::Login { interactive } => {
-            if interactive {
-                login_with_console(&profile_name, &mut config).await?
-            } else {
-                login_with_browser(&profile_name, &mut config).await?
+        Commands::Prompt => {
+            // Only the configuration file is read, a prompt must never wait on the network.
+            println!("{}", config.prompt(Some(profile_name)))
+        }
+
+        Commands::Version => println!("{}", VERSION.describe(options.format)?),
+
+        Commands::Whoami => {
+            let profile_name = config.current_profile(Some(profile_name));
+            let profile = config.get_profile(Some(profile_name.clone()))?;
+            let client = http_client(insecure)?;
+            let identity = whoami(&client, &endpoints, profile_name, &profile).await?;
+            println!("{}", identity.describe(options.format)?)
+        }
+
+        Commands::Login {
+            interactive,
+            client_id,
+            secret,
+            email,
+            keyring,
+            command,
+        } => {
+            ensure!(
+                profile_name != STDIN_PROFILE,
+                "cannot login to the profile read from the standard input"
+            );
+            match command {
+                None if client_id.is_some() => {
+                    let client = http_client(insecure)?;
+                    login_with_credentials(
+                        &client,
+                        &endpoints,
+                        &profile_name,
+                        &mut config,
+                        client_id.as_deref().unwrap_or_default(),
+                        secret.as_deref().unwrap_or_default(),
+                        email,
+                    )
+                    .await?
+                }
+                Some(LoginCommand::Device) => {
+                    let client = http_client(insecure)?;
+                    login_with_device(&client, &endpoints, &profile_name, &mut config).await?
+                }
+                None if interactive => {
+                    let client = http_client(insecure)?;
+                    login_with_console(&client, &endpoints, &profile_name, &mut config).await?
+                }
+                None => login_with_browser(&endpoints, &profile_name, &mut config).await?,
+            }
+            if use_keyring(keyring) {
+                // Logging in never fails for want of a keyring.
+                if let Err(error) = config.move_to_keyring(&profile_name, &SystemKeyring) {
+                    eprintln!(
+                        "Warning: {:#}. The app password is stored in plaintext in {}",
+                        error,
+                        config.path().display()
+                    );
+                }
+            }
+        }
+
+        Commands::Logout { all, revoke } => {
+            ensure!(
+                profile_name != STDIN_PROFILE,
+                "cannot logout of the profile read from the standard input"
+            );
+            let names = config.stored_profiles(Some(profile_name), all);
+            if names.is_empty() {
+                println!("Not logged in, there is nothing to log out of.");
+                return Ok(());
+            }
+
+            // Every app password is revoked before any profile is removed,
+            // so a failure leaves the configuration file untouched.
+            if revoke {
+                let client = http_client(insecure)?;
+                for name in &names {
+                    let profile = config.get_profile(Some(name.clone()))?;
+                    let valid_profile = profile
+                        .validate(&client, &endpoints)
+                        .await
+                        .with_context(|| format!("failed to validate profile {}", name))?;
+                    revoke_password(&client, &valid_profile)
+                        .await
+                        .with_context(|| {
+                            format!("failed to revoke the app password of {}", name)
+                        })?;
+                }
+            }
+            for account in config.remove_profiles(&names) {
+                if let Err(error) = SystemKeyring.delete(&account) {
+                    eprintln!(
+                        "Warning: failed to remove an app password from the keyring: {:#}",
+                        error
+                    );
+                }
+            }
+            let mut token_cache = TokenCache::load(config.path());
+            if token_cache.remove(&names) {
+                token_cache.save(config.path())?;
+            }
+            for name in names {
+                println!("Logged out of profile {}", name);
             }
         }
 
+        Commands::Profiles { command } => match command {
+            ProfilesCommand::List => {
+                let profiles = config.list_profiles(secret_policy);
+                if profiles.is_empty() {
+                    println!("No profiles configured, run `mz login`.");
+                } else {
+                    print_paged(&profiles.join("\n"), no_pager)?;
+                }
+            }
+            ProfilesCommand::Export { all, output } => {
+                let profile = (!all).then(|| config.current_profile(Some(profile_name)));
+                let bundle =
+                    config.export_profiles(profile.as_deref(), secret_policy, &SystemKeyring)?;
+                match output {
+                    // The bundle may hold app passwords, so only the user can read it.
+                    Some(output) => write_private(&output, &bundle)
+                        .context("failed to write the profiles bundle")?,
+                    None => print!("{}", bundle),
+                }
+            }
+            ProfilesCommand::Import { file, on_conflict } => {
+                let bundle = match file {
+                    Some(file) => {
+                        fs::read_to_string(file).context("failed to read the profiles bundle")?
+                    }
+                    None => {
+                        let mut bundle = String::new();
+                        std::io::stdin()
+                            .read_to_string(&mut bundle)
+                            .context("failed to read the profiles bundle")?;
+                        bundle
+                    }
+                };
+                for name in config.import_profiles(&bundle, on_conflict)? {
+                    println!("Imported profile {}", name);
+                }
+            }
+            ProfilesCommand::Doctor { .. } => {
+                unreachable!("handled before loading the configuration")
+            }
+        },
+
         Commands::Region { command } => {
-            let client = Client::new();
+            let client = http_client(insecure)?;
 
             match command {
                 RegionCommand::Enable {
                     cloud_provider_region,
+                    from_file,
+                    debug_http,
+                    no_rollback,
+                    wait,
+                    timeout,
+                } => {
+                    let cloud_provider_region = match from_file {
+                        Some(path) => RegionConfig::load(&path)?.region,
+                        None => CloudProviderRegion::from_str(
+                            &cloud_provider_region.context("missing region")?,
+                        )?,
+                    };
+                    let hooks = config.hooks().clone();
+                    let hook_profile = config.current_profile(Some(profile_name.clone()));
+                    let profile = config.get_profile(Some(profile_name))?;
+
+                    let valid_profile = profile
+                        .validate(&client, &endpoints)
+                        .await
+                        .context("failed to validate profile. reauthorize using mz login")?;
+
+                    let loading_spinner =
+                        run_loading_spinner("Enabling region...".to_string(), options.format);
+                    let enabled = async {
+                        let cloud_provider = get_provider_by_region_name(
+                            &client,
+                            &valid_profile,
+                            &cloud_provider_region,
+                        )
+                        .await
+                        .with_context(|| "Retrieving cloud provider.")?;
+
+                        let environment = match enable_region_environment(
+                            &client,
+                            &cloud_provider,
+                            &valid_profile,
+                            debug_http,
+                            secret_policy,
+                        )
+                        .await
+                        .with_context(|| "Enabling region.")?
+                        {
+                            Some(region) => {
+                                let setup = async {
+                                    get_region_environment(&client, &valid_profile, &region)
+                                        .await
+                                        .with_context(|| "Retrieving environment data.")
+                                };
+                                let rollback = (!no_rollback).then(|| {
+                                    delete_region_environment(
+                                        &client,
+                                        &cloud_provider,
+                                        &valid_profile,
+                                    )
+                                });
+                                Some(
+                                    setup_or_rollback(&cloud_provider_region, setup, rollback)
+                                        .await?,
+                                )
+                            }
+                            // An existing region is never rolled back.
+                            None => None,
+                        };
+                        let newly_enabled = environment.is_some();
+
+                        // A new region is always waited on, an existing one only with --wait.
+                        let environment = match environment {
+                            None if wait => Some(
+                                get_provider_region_environment(
+                                    &client,
+                                    &valid_profile,
+                                    &cloud_provider_region,
+                                )
+                                .await
+                                .with_context(|| "Retrieving environment data.")?,
+                            ),
+                            environment => environment,
+                        };
+                        if let Some(environment) = environment {
+                            loading_spinner.set_message("Waiting for the region to be healthy...");
+                            wait_until_healthy(
+                                || check_environment_health(&valid_profile, &environment),
+                                HEALTH_POLL_INTERVAL,
+                                timeout.map(Duration::from_secs),
+                            )
+                            .await?;
+                        }
+                        Ok(newly_enabled)
+                    }
+                    .await;
+
+                    // A failing hook does not undo the enable.
+                    if let Err(error) =
+                        run_enable_hook(&hooks, &hook_profile, cloud_provider_region, &enabled)
+                    {
+                        eprintln!("Warning: {:#}", error);
+                    }
+                    let message = if enabled? {
+                        "Region enabled."
+                    } else {
+                        "Region already enabled."
+                    };
+                    finish_loading_spinner(loading_spinner, message);
+
+                    if options.format == OutputFormat::Text {
+                        let environment = get_provider_region_environment(
+                            &client,
+                            &valid_profile,
+                            &cloud_provider_region,
+                        )
+                        .await
+                        .with_context(|| "Retrieving environment data.")?;
+                        println!(
+                            "{}",
+                            format_connection_details(
+                                &environment,
+                                profile.get_email(),
+                                profile.get_app_password(),
+                                secret_policy,
+                            )?
+                        );
+                    }
+                }
+
+                RegionCommand::Delete {
+                    cloud_provider_region,
+                    yes,
                 } => {
                     let cloud_provider_region =
                         CloudProviderRegion::from_str(&cloud_provider_region)?;
+                    if !yes {
+                        let confirmed = warning_delete_region(
+                            cloud_provider_region,
+                            &mut std::io::stdin().lock(),
+                            &mut std::io::stderr(),
+                        )?;
+                        ensure!(
+                            confirmed,
+                            "the typed name does not match, so the region was not deleted"
+                        );
+                    }
                     let profile = config.get_profile(Some(profile_name))?;
 
                     let valid_profile = profile
-                        .validate(&client)
+                        .validate(&client, &endpoints)
                         .await
                         .context("failed to validate profile. reauthorize using mz login")?;
 
-                    let loading_spinner = run_loading_spinner("Enabling region...".to_string());
+                    let loading_spinner =
+                        run_loading_spinner("Deleting region...".to_string(), options.format);
                     let cloud_provider = get_provider_by_region_name(
                         &client,
                         &valid_profile,
@@ -251,33 +942,22 @@ async fn main() -> Result<()> {
                     )
                     .await
                     .with_context(|| "Retrieving cloud provider.")?;
+                    delete_region_environment(&client, &cloud_provider, &valid_profile).await?;
 
-                    let region =
-                        enable_region_environment(&client, &cloud_provider, &valid_profile)
-                            .await
-                            .with_context(|| "Enabling region.")?;
-
-                    let environment = get_region_environment(&client, &valid_profile, &region)
-                        .await
-                        .with_context(|| "Retrieving environment data.")?;
-
-                    loop {
-                        if check_environment_health(&valid_profile, &environment)? {
-                            break;
-                        }
-                    }
-
-                    loading_spinner.finish_with_message("Region enabled.");
+                    finish_loading_spinner(loading_spinner, "Region deleted.");
                 }
 
-                RegionCommand::List => {
+                RegionCommand::List { output } => {
+                    let format = output.map(OutputFormat::from).unwrap_or(options.format);
                     let profile = config.get_profile(Some(profile_name))?;
 
                     let valid_profile = profile
-                        .validate(&client)
+                        .validate(&client, &endpoints)
                         .await
                         .context("failed to validate profile. reauthorize using mz login")?;
 
+                    let loading_spinner =
+                        run_loading_spinner("Listing regions...".to_string(), format);
                     let cloud_providers = list_cloud_providers(&client, &valid_profile)
                         .await
                         .with_context(|| "Retrieving cloud providers.")?;
@@ -285,15 +965,62 @@ async fn main() -> Result<()> {
                         list_regions(&cloud_providers, &client, &valid_profile)
                             .await
                             .with_context(|| "Listing regions.")?;
-                    cloud_providers_regions
-                        .iter()
-                        .for_each(|cloud_provider_region| {
-                            print_region_enabled(cloud_provider_region);
-                        });
+                    match format {
+                        OutputFormat::Text => {
+                            loading_spinner.finish_and_clear();
+                            let listing = cloud_providers_regions
+                                .iter()
+                                .map(format_region_enabled)
+                                .collect::<Vec<_>>()
+                                .join("\n");
+                            print_paged(&listing, no_pager)?;
+                        }
+                        OutputFormat::Json => {
+                            let listing = list_regions_json(
+                                &cloud_providers_regions,
+                                &client,
+                                &valid_profile,
+                            )
+                            .await?;
+                            println!("{}", listing);
+                        }
+                    }
+                }
+
+                RegionCommand::ConnectionString {
+                    cloud_provider_region,
+                    style,
+                } => {
+                    let cloud_provider_region =
+                        CloudProviderRegion::from_str(&cloud_provider_region)?;
+                    let profile = config.get_profile(Some(profile_name))?;
+
+                    let valid_profile = profile
+                        .validate(&client, &endpoints)
+                        .await
+                        .context("failed to validate profile. reauthorize using mz login")?;
+
+                    let environment = get_provider_region_environment(
+                        &client,
+                        &valid_profile,
+                        &cloud_provider_region,
+                    )
+                    .await
+                    .with_context(|| "Retrieving cloud provider region.")?;
+                    println!(
+                        "{}",
+                        connection_string(
+                            &environment,
+                            profile.get_email(),
+                            Some(profile.get_app_password()),
+                            style,
+                        )?
+                    );
                 }
 
                 RegionCommand::Status {
                     cloud_provider_region,
+                    cert_warn_days,
                 } => {
                     let cloud_provider_region =
                         CloudProviderRegion::from_str(&cloud_provider_region)?;
@@ -301,7 +1028,7 @@ async fn main() -> Result<()> {
                     let profile = config.get_profile(Some(profile_name))?;
 
                     let valid_profile = profile
-                        .validate(&client)
+                        .validate(&client, &endpoints)
                         .await
                         .context("failed to validate profile. reauthorize using mz login")?;
 
@@ -313,29 +1040,380 @@ async fn main() -> Result<()> {
                     .await
                     .with_context(|| "Retrieving cloud provider region.")?;
                     let health = check_environment_health(&valid_profile, &environment)?;
+                    let certificate_expiry = match cert_warn_days {
+                        Some(warn_days) => {
+                            let days = get_certificate_expiry_days(&environment)
+                                .await
+                                .with_context(|| "Retrieving TLS certificate.")?;
+                            Some((days, warn_days))
+                        }
+                        None => None,
+                    };
 
-                    print_environment_status(environment, health);
+                    print_environment_status(environment, health, certificate_expiry);
+                    ensure!(health, RegionUnhealthy);
+                }
+
+                RegionCommand::Watch {
+                    cloud_provider_region,
+                    interval,
+                    no_cache,
+                } => {
+                    let cloud_provider_region =
+                        CloudProviderRegion::from_str(&cloud_provider_region)?;
+
+                    let profile = config.get_profile(Some(profile_name))?;
+
+                    let valid_profile = profile
+                        .validate(&client, &endpoints)
+                        .await
+                        .context("failed to validate profile. reauthorize using mz login")?;
+
+                    let header = format!(
+                        "Every {}s: mz region status {}",
+                        interval, cloud_provider_region
+                    );
+                    let client = &client;
+                    let valid_profile = &valid_profile;
+                    let cloud_provider_region = &cloud_provider_region;
+                    let ttl = if no_cache {
+                        Duration::ZERO
+                    } else {
+                        HEALTH_CACHE_TTL
+                    };
+                    let health_cache = &RefCell::new(HealthCache::new(ttl, Instant::now));
+                    let render = move || async move {
+                        let status = async {
+                            let environment = get_provider_region_environment(
+                                client,
+                                valid_profile,
+                                cloud_provider_region,
+                            )
+                            .await
+                            .with_context(|| "Retrieving cloud provider region.")?;
+                            let health = health_cache
+                                .borrow_mut()
+                                .get(&environment.environmentd_pgwire_address, || {
+                                    check_environment_health(valid_profile, &environment)
+                                })?;
+                            Ok::<_, anyhow::Error>(format_environment_status(&environment, health))
+                        };
+                        // Keep watching through transient failures.
+                        match status.await {
+                            Ok(status) => status,
+                            Err(error) => format!("Error: {:#}", error),
+                        }
+                    };
+                    let tick = || async {
+                        tokio::time::sleep(Duration::from_secs(interval)).await;
+                        true
+                    };
+
+                    // Like any other command, the watch ends on Ctrl-C,
+                    // which exits with code 130.
+                    let mut stdout = std::io::stdout();
+                    watch(
+                        &mut stdout,
+                        atty::is(atty::Stream::Stdout),
+                        &header,
+                        render,
+                        tick,
+                    )
+                    .await?;
                 }
             }
         }
 
         Commands::Shell {
             cloud_provider_region,
+            completion,
+            statement_timeout,
+            timing,
+            fetch_size,
+            transcript,
+            command,
+            file,
+            variables,
+            parameters,
+            copy,
         } => {
             let cloud_provider_region = CloudProviderRegion::from_str(&cloud_provider_region)?;
             let profile = config.get_profile(Some(profile_name))?;
 
-            let client = Client::new();
+            let client = http_client(insecure)?;
             let valid_profile = profile
-                .validate(&client)
+                .validate(&client, &endpoints)
                 .await
                 .context("failed to validate profile. reauthorize using mz login")?;
 
-            shell(client, valid_profile, cloud_provider_region)
-                .await
-                .with_context(|| "Running shell")?;
+            shell(
+                client,
+                valid_profile,
+                cloud_provider_region,
+                completion,
+                PsqlSettings {
+                    statement_timeout,
+                    timing,
+                    fetch_size,
+                    transcript,
+                    command,
+                    file,
+                    variables,
+                    parameters,
+                    copy,
+                    history: None,
+                },
+                no_pager,
+                secret_policy,
+            )
+            .await
+            .with_context(|| "Running shell")?;
         }
     }
 
     config.close()
 }
+
+/// Reports the problems of the configuration file, repairing the safe ones if asked to.
+fn profiles_doctor(path: &Path, fix: bool) -> Result<()> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            println!("No configuration file found. Login using mz login.");
+            return Ok(());
+        }
+        Err(error) => return Err(error).context("failed to read configuration file"),
+    };
+
+    let problems = Configuration::diagnose(&contents);
+    if fix && problems.iter().any(|problem| problem.is_fixable()) {
+        if let Some(repaired) = Configuration::repair(&contents)? {
+            write_private(path, &repaired).context("failed to write out updated configuration")?;
+        }
+    }
+
+    let mut remaining = 0;
+    for problem in &problems {
+        if fix && problem.is_fixable() {
+            println!("Fixed: {}", problem);
+        } else if problem.is_fixable() {
+            remaining += 1;
+            println!("{}. Repair it with mz profiles doctor --fix", problem);
+        } else {
+            remaining += 1;
+            println!("{}", problem);
+        }
+    }
+    if problems.is_empty() {
+        println!("No problems found in {}", path.display());
+    }
+
+    ensure!(remaining == 0, "{} problems found", remaining);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{CommandFactory, Parser};
+    use clap_complete::Shell;
+
+    use super::{Cli, Commands, GlobalOptions, RegionCommand};
+    use crate::completions::CompletionShell;
+    use crate::configuration::Configuration;
+    use crate::region::ListOutput;
+    use crate::utils::OutputFormat;
+
+    #[test]
+    fn test_profile_defaults() {
+        let config = toml::from_str::<Configuration>(&format!(
+            r#"
+current_profile = "default"
+
+[profiles.default]
+email = "default@materialize.com"
+app-password = "mzp_{}"
+
+[profiles.default.defaults]
+format = "json"
+"#,
+            "1".repeat(64)
+        ))
+        .unwrap();
+        let defaults = config.get_profile_defaults(Some("default".to_string()));
+
+        let args = Cli::parse_from(["mz", "region", "list"]);
+        let options = GlobalOptions::new(&args, &defaults);
+        assert_eq!(options.format, OutputFormat::Json);
+        assert!(!options.no_pager);
+
+        let args = Cli::parse_from(["mz", "region", "list", "--format", "text"]);
+        let options = GlobalOptions::new(&args, &defaults);
+        assert_eq!(options.format, OutputFormat::Text);
+
+        let defaults = config.get_profile_defaults(Some("missing".to_string()));
+        let args = Cli::parse_from(["mz", "region", "list"]);
+        assert_eq!(
+            GlobalOptions::new(&args, &defaults).format,
+            OutputFormat::Text
+        );
+    }
+
+    #[test]
+    fn test_profile_env() {
+        // The flag takes precedence over `MZ_PROFILE`, which takes precedence
+        // over the default profile.
+        let unset = |_: &str| None;
+        let staging = |name: &str| (name == "MZ_PROFILE").then(|| "staging".to_string());
+        let profile = |args: &[&str], var: &dyn Fn(&str) -> Option<String>| {
+            Cli::parse_from(args).profile_with_env(var)
+        };
+
+        assert_eq!(profile(&["mz", "region", "list"], &unset), "default");
+        assert_eq!(profile(&["mz", "prompt"], &staging), "staging");
+        assert_eq!(
+            profile(&["mz", "--profile", "test", "login"], &staging),
+            "test"
+        );
+        assert_eq!(
+            profile(&["mz", "prompt"], &|_| Some(String::new())),
+            "default"
+        );
+    }
+
+    #[test]
+    fn test_shell_file() {
+        let args = Cli::parse_from(["mz", "shell", "aws/us-east-1", "-f", "setup.sql"]);
+        assert!(matches!(
+            args.command,
+            Some(Commands::Shell { file: Some(file), command: None, .. })
+                if file.to_str() == Some("setup.sql")
+        ));
+        assert!(Cli::try_parse_from([
+            "mz",
+            "shell",
+            "aws/us-east-1",
+            "--file",
+            "setup.sql",
+            "--command",
+            "SELECT 1"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_reads_stdin() {
+        let reads_stdin = |args: &[&str]| {
+            let args = Cli::parse_from(["mz"].iter().chain(args));
+            args.command.unwrap().reads_stdin()
+        };
+        assert!(reads_stdin(&["profiles", "import"]));
+        assert!(!reads_stdin(&["profiles", "import", "bundle.toml"]));
+        assert!(reads_stdin(&["region", "delete", "aws/us-east-1"]));
+        assert!(!reads_stdin(&[
+            "region",
+            "delete",
+            "aws/us-east-1",
+            "--yes"
+        ]));
+        assert!(reads_stdin(&["shell", "aws/us-east-1"]));
+        assert!(reads_stdin(&["shell", "aws/us-east-1", "-f", "-"]));
+        assert!(!reads_stdin(&["shell", "aws/us-east-1", "-f", "setup.sql"]));
+        assert!(!reads_stdin(&["shell", "aws/us-east-1", "-c", "SELECT 1"]));
+        assert!(!reads_stdin(&[
+            "shell",
+            "aws/us-east-1",
+            "--completion",
+            "mz_"
+        ]));
+        assert!(!reads_stdin(&["region", "list"]));
+    }
+
+    #[test]
+    fn test_login_credentials() {
+        let args = Cli::parse_from([
+            "mz",
+            "login",
+            "--client-id",
+            "4b4a1b0e-0000-0000-0000-000000000000",
+            "--secret",
+            "hunter2",
+        ]);
+        assert!(matches!(
+            args.command,
+            Some(Commands::Login {
+                client_id: Some(_),
+                secret: Some(_),
+                email: None,
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["mz", "login", "--client-id", "ci"]).is_err());
+        assert!(Cli::try_parse_from(["mz", "login", "--email", "ci@example.com"]).is_err());
+    }
+
+    #[test]
+    fn test_verbose() {
+        assert_eq!(Cli::parse_from(["mz", "region", "list"]).verbose, 0);
+        assert_eq!(Cli::parse_from(["mz", "-v", "region", "list"]).verbose, 1);
+        assert_eq!(Cli::parse_from(["mz", "region", "list", "-vv"]).verbose, 2);
+    }
+
+    #[test]
+    fn test_completions() {
+        let args = Cli::parse_from(["mz", "completions", "bash"]);
+        assert!(matches!(
+            args.command,
+            Some(Commands::Completions {
+                shell: CompletionShell::Bash
+            })
+        ));
+        assert!(Cli::try_parse_from(["mz", "completions", "tcsh"]).is_err());
+
+        let mut bash = vec![];
+        clap_complete::generate(Shell::Bash, &mut Cli::command(), "mz", &mut bash);
+        let bash = String::from_utf8(bash).unwrap();
+        assert!(bash.contains("mz__region)"));
+        assert!(bash.contains("mz__shell)"));
+    }
+
+    #[test]
+    fn test_region_list_output() {
+        let output = |args: &[&str]| match Cli::parse_from(args).command {
+            Some(Commands::Region {
+                command: RegionCommand::List { output },
+            }) => output,
+            command => panic!("unexpected command {:?}", command),
+        };
+        assert_eq!(output(&["mz", "region", "list"]), None);
+        assert_eq!(
+            output(&["mz", "region", "list", "-o", "json"]),
+            Some(ListOutput::Json)
+        );
+        assert_eq!(
+            output(&["mz", "region", "list", "--output", "table"]),
+            Some(ListOutput::Table)
+        );
+        assert!(Cli::try_parse_from(["mz", "region", "list", "-o", "text"]).is_err());
+
+        assert_eq!(OutputFormat::from(ListOutput::Table), OutputFormat::Text);
+        assert_eq!(OutputFormat::from(ListOutput::Json), OutputFormat::Json);
+    }
+
+    #[test]
+    fn test_region_enable_from_file() {
+        assert!(
+            Cli::try_parse_from(["mz", "region", "enable", "--from-file", "region.toml"]).is_ok()
+        );
+        assert!(Cli::try_parse_from(["mz", "region", "enable", "aws/us-east-1"]).is_ok());
+        assert!(Cli::try_parse_from(["mz", "region", "enable"]).is_err());
+        assert!(Cli::try_parse_from([
+            "mz",
+            "region",
+            "enable",
+            "aws/us-east-1",
+            "--from-file",
+            "region.toml"
+        ])
+        .is_err());
+    }
+}
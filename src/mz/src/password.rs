@@ -9,9 +9,9 @@
 
 use std::collections::HashMap;
 
-use crate::configuration::ValidProfile;
-use crate::{FronteggAppPassword, API_FRONTEGG_TOKEN_AUTH_URL};
-use anyhow::{Context, Result};
+use crate::configuration::{FronteggAPIToken, ValidProfile};
+use crate::FronteggAppPassword;
+use anyhow::{ensure, Context, Result};
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
 use reqwest::Client;
 
@@ -20,26 +20,52 @@ pub(crate) async fn list_passwords(
     client: &Client,
     valid_profile: &ValidProfile<'_>,
 ) -> Result<Vec<FronteggAppPassword>> {
-    let authorization: String = format!("Bearer {}", valid_profile.frontegg_auth.access_token);
-
-    let mut headers = HeaderMap::new();
-    headers.insert(USER_AGENT, HeaderValue::from_static("reqwest"));
-    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-    headers.insert(
-        AUTHORIZATION,
-        HeaderValue::from_str(authorization.as_str()).unwrap(),
-    );
     let mut body = HashMap::new();
     body.insert("description", &"App password for the CLI");
 
-    client
-        .get(API_FRONTEGG_TOKEN_AUTH_URL)
-        .headers(headers)
-        .json(&body)
-        .send()
+    valid_profile
+        .send(client, |authorization| {
+            let mut headers = HeaderMap::new();
+            headers.insert(USER_AGENT, HeaderValue::from_static("reqwest"));
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+            headers.insert(AUTHORIZATION, HeaderValue::from_str(authorization).unwrap());
+            client
+                .get(&valid_profile.endpoints.frontegg_api_token_auth)
+                .headers(headers)
+                .json(&body)
+        })
         .await
         .context("failed to communicate with server")?
         .json::<Vec<FronteggAppPassword>>()
         .await
         .context("failed to parse results from server")
 }
+
+/// Revokes the app password of the profile, so that it can no longer be used.
+pub(crate) async fn revoke_password(
+    client: &Client,
+    valid_profile: &ValidProfile<'_>,
+) -> Result<()> {
+    let api_token: FronteggAPIToken = valid_profile.profile.get_app_password().try_into()?;
+    let url = format!(
+        "{}/{}",
+        valid_profile.endpoints.frontegg_api_token_auth, api_token.client_id
+    );
+
+    let response = valid_profile
+        .send(client, |authorization| {
+            let mut headers = HeaderMap::new();
+            headers.insert(USER_AGENT, HeaderValue::from_static("reqwest"));
+            headers.insert(AUTHORIZATION, HeaderValue::from_str(authorization).unwrap());
+            client.delete(&url).headers(headers)
+        })
+        .await
+        .context("failed to communicate with server")?;
+    ensure!(
+        response.status().is_success(),
+        "the server responded with {}",
+        response.status()
+    );
+
+    Ok(())
+}
@@ -8,35 +8,72 @@
 // by the Apache License, Version 2.0.
 
 use std::collections::HashMap;
+use std::fs;
+use std::future::Future;
+use std::io::{BufRead, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::time::{Duration, Instant};
 
 use crate::configuration::ValidProfile;
-use crate::{CloudProvider, CloudProviderAndRegion, Environment, Region, CLOUD_PROVIDERS_URL};
-use anyhow::{bail, ensure, Context, Result};
+use crate::shell::{connection_string, ConnectionStringStyle};
+use crate::utils::{redact_secrets, OutputFormat, RegionNotEnabled, SecretPolicy};
+use crate::{CloudProvider, CloudProviderAndRegion, Environment, Region};
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use clap::ArgEnum;
+use futures::stream::{self, StreamExt};
+use openssl::asn1::Asn1Time;
+use openssl::ssl::{SslConnector, SslMethod, SslVerifyMode};
+use openssl::x509::X509Ref;
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
-use reqwest::{Client, Error};
+use reqwest::{Client, StatusCode};
 use serde::de::{Unexpected, Visitor};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fmt::Display;
 use std::str::FromStr;
 
 /// Cloud providers and regions available.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum CloudProviderRegion {
     AwsUsEast1,
+    AwsUsWest2,
     AwsEuWest1,
+    AwsEuCentral1,
+    AwsApSoutheast2,
 }
 
 /// Implementation to name the possible values and parse every option.
 impl CloudProviderRegion {
-    pub fn variants() -> [&'static str; 2] {
-        ["aws/us-east-1", "aws/eu-west-1"]
+    pub fn variants() -> [&'static str; 5] {
+        [
+            "aws/us-east-1",
+            "aws/us-west-2",
+            "aws/eu-west-1",
+            "aws/eu-central-1",
+            "aws/ap-southeast-2",
+        ]
+    }
+
+    /// Return the name of the cloud provider.
+    pub fn provider_name(self) -> &'static str {
+        match self {
+            CloudProviderRegion::AwsUsEast1
+            | CloudProviderRegion::AwsUsWest2
+            | CloudProviderRegion::AwsEuWest1
+            | CloudProviderRegion::AwsEuCentral1
+            | CloudProviderRegion::AwsApSoutheast2 => "aws",
+        }
     }
 
     /// Return the region name inside a cloud provider.
     pub fn region_name(self) -> &'static str {
         match self {
             CloudProviderRegion::AwsUsEast1 => "us-east-1",
+            CloudProviderRegion::AwsUsWest2 => "us-west-2",
             CloudProviderRegion::AwsEuWest1 => "eu-west-1",
+            CloudProviderRegion::AwsEuCentral1 => "eu-central-1",
+            CloudProviderRegion::AwsApSoutheast2 => "ap-southeast-2",
         }
     }
 }
@@ -45,7 +82,10 @@ impl Display for CloudProviderRegion {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             CloudProviderRegion::AwsUsEast1 => write!(f, "aws/us-east-1"),
+            CloudProviderRegion::AwsUsWest2 => write!(f, "aws/us-west-2"),
             CloudProviderRegion::AwsEuWest1 => write!(f, "aws/eu-west-1"),
+            CloudProviderRegion::AwsEuCentral1 => write!(f, "aws/eu-central-1"),
+            CloudProviderRegion::AwsApSoutheast2 => write!(f, "aws/ap-southeast-2"),
         }
     }
 }
@@ -56,7 +96,10 @@ impl FromStr for CloudProviderRegion {
     fn from_str(s: &str) -> Result<Self> {
         match s {
             "aws/us-east-1" => Ok(CloudProviderRegion::AwsUsEast1),
+            "aws/us-west-2" => Ok(CloudProviderRegion::AwsUsWest2),
             "aws/eu-west-1" => Ok(CloudProviderRegion::AwsEuWest1),
+            "aws/eu-central-1" => Ok(CloudProviderRegion::AwsEuCentral1),
+            "aws/ap-southeast-2" => Ok(CloudProviderRegion::AwsApSoutheast2),
             _ => bail!("Unknown region {}", s),
         }
     }
@@ -102,6 +145,32 @@ impl Serialize for CloudProviderRegion {
     }
 }
 
+/// Region to enable, as defined in a TOML file.
+///
+/// E.g.:
+/// region = "aws/us-east-1"
+///
+/// The environment assignment takes no other parameter,
+/// so any other field is rejected.
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct RegionConfig {
+    pub(crate) region: CloudProviderRegion,
+}
+
+impl RegionConfig {
+    pub(crate) fn parse(contents: &str) -> Result<RegionConfig> {
+        toml::from_str(contents).context("invalid region definition")
+    }
+
+    pub(crate) fn load(path: &Path) -> Result<RegionConfig> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        RegionConfig::parse(&contents)
+            .with_context(|| format!("failed to parse {}", path.display()))
+    }
+}
+
 /// Build the headers for reqwest request with the frontegg authorization.
 fn build_region_request_headers(authorization: &str) -> HeaderMap {
     let mut headers = HeaderMap::new();
@@ -112,31 +181,208 @@ fn build_region_request_headers(authorization: &str) -> HeaderMap {
     headers
 }
 
+/// Describes an HTTP exchange for debugging, with its secrets masked
+/// unless the policy reveals them.
+pub(crate) fn debug_http_message(
+    url: &str,
+    request: &Value,
+    response: &Value,
+    secret_policy: SecretPolicy,
+) -> String {
+    let mut request = request.clone();
+    redact_secrets(&mut request, secret_policy);
+    let mut response = response.clone();
+    redact_secrets(&mut response, secret_policy);
+
+    format!("POST {}\nRequest: {}\nResponse: {}", url, request, response)
+}
+
 /// Enables a particular cloud provider's region
+///
+/// Returns `None` if the region was already enabled.
+///
+/// If `debug_http` is set, the request and the response are printed
+/// to the standard error, with their secrets masked per the policy.
 pub(crate) async fn enable_region_environment(
     client: &Client,
     cloud_provider: &CloudProvider,
     valid_profile: &ValidProfile<'_>,
-) -> Result<Region, reqwest::Error> {
-    let authorization: String = format!("Bearer {}", valid_profile.frontegg_auth.access_token);
-
-    let headers = build_region_request_headers(&authorization);
+    debug_http: bool,
+    secret_policy: SecretPolicy,
+) -> Result<Option<Region>> {
     let body: HashMap<char, char> = HashMap::new();
+    let url = format!(
+        "{:}/api/environmentassignment",
+        cloud_provider.region_controller_url
+    );
 
-    client
-        .post(
-            format!(
-                "{:}/api/environmentassignment",
-                cloud_provider.region_controller_url
+    let response = valid_profile
+        .send(client, |authorization| {
+            client
+                .post(url.as_str())
+                .headers(build_region_request_headers(authorization))
+                .json(&body)
+        })
+        .await?;
+    let status = response.status();
+    // The body of an error may not be JSON, which the status reports better.
+    let response = response.json::<Value>().await.unwrap_or(Value::Null);
+
+    if debug_http {
+        eprintln!(
+            "{}",
+            debug_http_message(
+                &url,
+                &serde_json::to_value(&body)?,
+                &response,
+                secret_policy
             )
-            .as_str(),
-        )
-        .headers(headers)
-        .json(&body)
-        .send()
-        .await?
-        .json::<Region>()
-        .await
+        );
+    }
+
+    enabled_region(status, response)
+}
+
+/// Reads the region out of the response to enabling it, or `None` if the
+/// region controller responded that the region already exists.
+fn enabled_region(status: StatusCode, response: Value) -> Result<Option<Region>> {
+    if status == StatusCode::CONFLICT {
+        return Ok(None);
+    }
+    ensure!(
+        status.is_success(),
+        "the region controller responded with {}",
+        status
+    );
+    Ok(Some(serde_json::from_value(response)?))
+}
+
+/// Deletes the environment assignment of a cloud provider's region,
+/// e.g. with `mz region delete` or to roll back a region whose setup failed.
+pub(crate) async fn delete_region_environment(
+    client: &Client,
+    cloud_provider: &CloudProvider,
+    valid_profile: &ValidProfile<'_>,
+) -> Result<()> {
+    let url = format!(
+        "{:}/api/environmentassignment",
+        cloud_provider.region_controller_url
+    );
+
+    let response = valid_profile
+        .send(client, |authorization| {
+            client
+                .delete(&url)
+                .headers(build_region_request_headers(authorization))
+        })
+        .await?;
+    if response.status() == StatusCode::NOT_FOUND {
+        bail!("Region is not enabled");
+    }
+    ensure!(
+        response.status().is_success(),
+        "the region controller responded with {}",
+        response.status()
+    );
+    Ok(())
+}
+
+/// Warns that deleting the region deletes all of its data, and asks to
+/// confirm by typing the region's name back.
+///
+/// Returns whether the deletion was confirmed.
+pub(crate) fn warning_delete_region<R, W>(
+    cloud_provider_region: CloudProviderRegion,
+    input: &mut R,
+    out: &mut W,
+) -> Result<bool>
+where
+    R: BufRead,
+    W: Write,
+{
+    write!(
+        out,
+        "WARNING: deleting the region {} deletes all of its data.\n\
+         Type the name of the region to confirm: ",
+        cloud_provider_region
+    )?;
+    out.flush()?;
+
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+    Ok(answer.trim() == cloud_provider_region.to_string())
+}
+
+/// How long to wait between probes of the health of a region being provisioned.
+pub(crate) const HEALTH_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Probes the health of a region until it is healthy, waiting the interval
+/// between probes.
+///
+/// Fails once the timeout, if any, elapses, as the region is still provisioning.
+pub(crate) async fn wait_until_healthy<F>(
+    mut healthy: F,
+    interval: Duration,
+    timeout: Option<Duration>,
+) -> Result<()>
+where
+    F: FnMut() -> Result<bool>,
+{
+    let wait = async {
+        while !healthy()? {
+            tokio::time::sleep(interval).await;
+        }
+        Ok(())
+    };
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, wait).await.map_err(|_| {
+            anyhow!(
+                "the region is still provisioning after {} seconds. check on it with mz region status",
+                timeout.as_secs()
+            )
+        })?,
+        None => wait.await,
+    }
+}
+
+/// Runs the setup that follows the creation of a region. If it fails, runs the
+/// rollback, if any, to delete the half-created region.
+///
+/// The error of the setup is returned with what the rollback cleaned up.
+pub(crate) async fn setup_or_rollback<T, S, R>(
+    cloud_provider_region: &CloudProviderRegion,
+    setup: S,
+    rollback: Option<R>,
+) -> Result<T>
+where
+    S: Future<Output = Result<T>>,
+    R: Future<Output = Result<()>>,
+{
+    let error = match setup.await {
+        Err(error) => error,
+        result => return result,
+    };
+    let rollback = match rollback {
+        Some(rollback) => rollback,
+        None => {
+            return Err(error.context(format!(
+                "{} was created but its setup failed, and it was kept (--no-rollback)",
+                cloud_provider_region
+            )))
+        }
+    };
+
+    match rollback.await {
+        Err(rollback_error) => Err(error.context(format!(
+            "{} was created but its setup failed, and deleting it also failed ({:#}), \
+             so it may need to be deleted manually",
+            cloud_provider_region, rollback_error
+        ))),
+        _ => Err(error.context(format!(
+            "{} was created but its setup failed, so it was deleted",
+            cloud_provider_region
+        ))),
+    }
 }
 
 //// Get a cloud provider's regions
@@ -145,12 +391,16 @@ pub(crate) async fn get_cloud_provider_region_details(
     cloud_provider_region: &CloudProvider,
     valid_profile: &ValidProfile<'_>,
 ) -> Result<Vec<Region>, anyhow::Error> {
-    let authorization: String = format!("Bearer {}", valid_profile.frontegg_auth.access_token);
-    let headers = build_region_request_headers(&authorization);
     let mut region_api_url = cloud_provider_region.region_controller_url.clone();
     region_api_url.push_str("/api/environmentassignment");
 
-    let response = client.get(region_api_url).headers(headers).send().await?;
+    let response = valid_profile
+        .send(client, |authorization| {
+            client
+                .get(&region_api_url)
+                .headers(build_region_request_headers(authorization))
+        })
+        .await?;
     ensure!(response.status().is_success());
     Ok(response.json::<Vec<Region>>().await?)
 }
@@ -160,15 +410,19 @@ pub(crate) async fn region_environment_details(
     client: &Client,
     region: &Region,
     valid_profile: &ValidProfile<'_>,
-) -> Result<Option<Vec<Environment>>, Error> {
-    let authorization: String = format!("Bearer {}", valid_profile.frontegg_auth.access_token);
-    let headers = build_region_request_headers(authorization.as_str());
+) -> Result<Option<Vec<Environment>>> {
     let mut region_api_url = region.environment_controller_url
         [0..region.environment_controller_url.len() - 4]
         .to_string();
     region_api_url.push_str("/api/environment");
 
-    let response = client.get(region_api_url).headers(headers).send().await?;
+    let response = valid_profile
+        .send(client, |authorization| {
+            client
+                .get(&region_api_url)
+                .headers(build_region_request_headers(authorization))
+        })
+        .await?;
     match response.content_length() {
         Some(length) => {
             if length > 0 {
@@ -181,32 +435,55 @@ pub(crate) async fn region_environment_details(
     }
 }
 
+/// Maximum number of cloud providers whose regions are retrieved at once.
+const LIST_REGIONS_CONCURRENCY: usize = 4;
+
 /// List all the available regions for a list of cloud providers.
 pub(crate) async fn list_regions(
-    cloud_providers: &Vec<CloudProvider>,
+    cloud_providers: &[CloudProvider],
     client: &Client,
     valid_profile: &ValidProfile<'_>,
 ) -> Result<Vec<CloudProviderAndRegion>> {
-    // TODO: Run requests in parallel
-    let mut cloud_providers_and_regions: Vec<CloudProviderAndRegion> = Vec::new();
+    list_regions_with(cloud_providers, |cloud_provider| {
+        get_cloud_provider_region_details(client, cloud_provider, valid_profile)
+    })
+    .await
+}
 
-    for cloud_provider in cloud_providers {
-        let cloud_provider_region_details =
-            get_cloud_provider_region_details(client, cloud_provider, valid_profile)
+/// Retrieves the regions of the cloud providers concurrently, sorted by
+/// provider then region.
+async fn list_regions_with<'a, F, Fut>(
+    cloud_providers: &'a [CloudProvider],
+    get_region_details: F,
+) -> Result<Vec<CloudProviderAndRegion>>
+where
+    F: Fn(&'a CloudProvider) -> Fut,
+    Fut: Future<Output = Result<Vec<Region>>>,
+{
+    let requests = cloud_providers.iter().map(|cloud_provider| {
+        let region_details = get_region_details(cloud_provider);
+        async move {
+            let cloud_provider_region_details = region_details
                 .await
                 .with_context(|| "Retrieving region details.")?;
-        match cloud_provider_region_details.get(0) {
-            Some(region) => cloud_providers_and_regions.push(CloudProviderAndRegion {
-                cloud_provider: cloud_provider.clone(),
-                region: Some(region.to_owned()),
-            }),
-            None => cloud_providers_and_regions.push(CloudProviderAndRegion {
+            Ok(CloudProviderAndRegion {
                 cloud_provider: cloud_provider.clone(),
-                region: None,
-            }),
+                region: cloud_provider_region_details.first().cloned(),
+            })
         }
-    }
+    });
+    let mut cloud_providers_and_regions = stream::iter(requests)
+        .buffered(LIST_REGIONS_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?;
 
+    cloud_providers_and_regions.sort_by(|a, b| {
+        let a = &a.cloud_provider;
+        let b = &b.cloud_provider;
+        (&a.provider, &a.region).cmp(&(&b.provider, &b.region))
+    });
     Ok(cloud_providers_and_regions)
 }
 
@@ -216,37 +493,108 @@ pub(crate) async fn list_regions(
 pub(crate) async fn list_cloud_providers(
     client: &Client,
     valid_profile: &ValidProfile<'_>,
-) -> Result<Vec<CloudProvider>, Error> {
-    let authorization: String = format!("Bearer {}", valid_profile.frontegg_auth.access_token);
-
-    let headers = build_region_request_headers(&authorization);
-
-    client
-        .get(CLOUD_PROVIDERS_URL)
-        .headers(headers)
-        .send()
+) -> Result<Vec<CloudProvider>> {
+    Ok(valid_profile
+        .send(client, |authorization| {
+            client
+                .get(&valid_profile.endpoints.cloud_providers)
+                .headers(build_region_request_headers(authorization))
+        })
         .await?
         .json::<Vec<CloudProvider>>()
-        .await
+        .await?)
 }
 
-/// Prints if a region is enabled or not
+/// Formats if a region is enabled or not
 ///
 /// E.g.: AWS/us-east-1  enabled
-pub(crate) fn print_region_enabled(cloud_provider_and_region: &CloudProviderAndRegion) {
+pub(crate) fn format_region_enabled(cloud_provider_and_region: &CloudProviderAndRegion) -> String {
     let region = &cloud_provider_and_region.region;
     let cloud_provider = &cloud_provider_and_region.cloud_provider;
 
     match region {
-        Some(_) => println!(
+        Some(_) => format!(
             "{:}/{:}  enabled",
             cloud_provider.provider, cloud_provider.region
         ),
-        None => println!(
+        None => format!(
             "{:}/{:}  disabled",
             cloud_provider.provider, cloud_provider.region
         ),
-    };
+    }
+}
+
+/// How `mz region list` prints the regions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ArgEnum)]
+pub(crate) enum ListOutput {
+    /// One line per region
+    Table,
+    /// A JSON array of the regions, with the addresses of their environments
+    Json,
+}
+
+impl From<ListOutput> for OutputFormat {
+    fn from(output: ListOutput) -> OutputFormat {
+        match output {
+            ListOutput::Table => OutputFormat::Text,
+            ListOutput::Json => OutputFormat::Json,
+        }
+    }
+}
+
+/// A region as listed when using the JSON format.
+#[derive(Debug, Serialize)]
+pub(crate) struct RegionListing {
+    provider: String,
+    region: String,
+    enabled: bool,
+    pgwire_address: Option<String>,
+    https_address: Option<String>,
+}
+
+impl RegionListing {
+    /// Lists the region with the addresses of its environment, if it has one.
+    pub(crate) fn new(
+        cloud_provider_and_region: &CloudProviderAndRegion,
+        environment: Option<&Environment>,
+    ) -> RegionListing {
+        let cloud_provider = &cloud_provider_and_region.cloud_provider;
+        RegionListing {
+            provider: cloud_provider.provider.clone(),
+            region: cloud_provider.region.clone(),
+            enabled: cloud_provider_and_region.region.is_some(),
+            pgwire_address: environment.map(|e| e.environmentd_pgwire_address.clone()),
+            https_address: environment.map(|e| e.environmentd_https_address.clone()),
+        }
+    }
+}
+
+/// Lists the regions with the addresses of their environments, as a JSON array.
+pub(crate) async fn list_regions_json(
+    cloud_providers_regions: &[CloudProviderAndRegion],
+    client: &Client,
+    valid_profile: &ValidProfile<'_>,
+) -> Result<String> {
+    let requests = cloud_providers_regions
+        .iter()
+        .map(|cloud_provider_and_region| async move {
+            let environments = match &cloud_provider_and_region.region {
+                Some(region) => region_environment_details(client, region, valid_profile)
+                    .await
+                    .with_context(|| "Retrieving environment details.")?,
+                None => None,
+            };
+            let environment = environments.as_ref().and_then(|e| e.first());
+            Ok(RegionListing::new(cloud_provider_and_region, environment))
+        });
+    let listings = stream::iter(requests)
+        .buffered(LIST_REGIONS_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(serde_json::to_string(&listings)?)
 }
 
 ///
@@ -255,23 +603,227 @@ pub(crate) fn print_region_enabled(cloud_provider_and_region: &CloudProviderAndR
 /// Healthy:         {yes/no}
 /// SQL address:     foo.materialize.cloud:6875
 /// HTTPS address:   <https://foo.materialize.cloud>
-pub(crate) fn print_environment_status(environment: Environment, health: bool) {
-    if health {
-        println!("Healthy:\tyes");
-    } else {
-        println!("Healthy:\tno");
+/// TLS expiry:      {days} days
+pub(crate) fn print_environment_status(
+    environment: Environment,
+    health: bool,
+    certificate_expiry: Option<(i32, u32)>,
+) {
+    println!("{}", format_environment_status(&environment, health));
+
+    if let Some((days, warn_days)) = certificate_expiry {
+        println!("TLS expiry: \t{} days", days);
+        if let Some(warning) = certificate_expiry_warning(days, warn_days) {
+            eprintln!("{}", warning);
+        }
     }
-    println!(
-        "SQL address: \t{}",
-        &environment.environmentd_pgwire_address
-            [0..environment.environmentd_pgwire_address.len() - 5]
-    );
+}
+
+/// Formats an environment's health and addresses, as printed
+/// by `mz region status` and rendered by `mz region watch`.
+pub(crate) fn format_environment_status(environment: &Environment, health: bool) -> String {
+    let health = if health { "yes" } else { "no" };
+    // Remove port from urls
+    let pgwire_address = &environment.environmentd_pgwire_address
+        [0..environment.environmentd_pgwire_address.len() - 5];
+    let https_address = &environment.environmentd_https_address
+        [0..environment.environmentd_https_address.len() - 4];
+
+    format!(
+        "Healthy:\t{}\nSQL address: \t{}\nHTTPS address: \thttps://{}",
+        health, pgwire_address, https_address
+    )
+}
+
+/// Formats the addresses of an enabled region, and the psql command to
+/// connect to it, as printed by `mz region enable`.
+///
+/// The app password is only part of the connection string when secrets are
+/// revealed; otherwise psql prompts for it.
+pub(crate) fn format_connection_details(
+    environment: &Environment,
+    email: &str,
+    app_password: &str,
+    secret_policy: SecretPolicy,
+) -> Result<String> {
+    let app_password = (secret_policy == SecretPolicy::Reveal).then_some(app_password);
+    let psql = connection_string(
+        environment,
+        email,
+        app_password,
+        ConnectionStringStyle::Psql,
+    )?;
     // Remove port from url
-    println!(
-        "HTTPS address: \thttps://{}",
-        &environment.environmentd_https_address
-            [0..environment.environmentd_https_address.len() - 4]
-    );
+    let https_address = &environment.environmentd_https_address
+        [0..environment.environmentd_https_address.len() - 4];
+
+    Ok(format!(
+        "SQL address: \t{}\nHTTPS address: \thttps://{}\nConnect with: \t{}",
+        environment.environmentd_pgwire_address, https_address, psql
+    ))
+}
+
+/// Default number of seconds between the refreshes of `mz region watch`.
+pub(crate) const WATCH_INTERVAL_SECS: u64 = 5;
+
+/// How long a probed health is reused for, unless caching is disabled.
+///
+/// It is shorter than the default watch interval, so that only faster
+/// refreshes reuse a probe.
+pub(crate) const HEALTH_CACHE_TTL: Duration = Duration::from_secs(3);
+
+/// Health of the environments, keyed by address.
+///
+/// An environment is probed again only once its cached health is older
+/// than the TTL. A zero TTL probes on every call.
+pub(crate) struct HealthCache<C> {
+    ttl: Duration,
+    now: C,
+    entries: HashMap<String, (Instant, bool)>,
+}
+
+impl<C> HealthCache<C>
+where
+    C: Fn() -> Instant,
+{
+    pub(crate) fn new(ttl: Duration, now: C) -> Self {
+        HealthCache {
+            ttl,
+            now,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Returns the health of the address, probing it if none is cached within the TTL.
+    pub(crate) fn get<P>(&mut self, address: &str, probe: P) -> Result<bool>
+    where
+        P: FnOnce() -> Result<bool>,
+    {
+        let now = (self.now)();
+        if let Some((probed_at, health)) = self.entries.get(address) {
+            if now.duration_since(*probed_at) < self.ttl {
+                return Ok(*health);
+            }
+        }
+
+        let health = probe()?;
+        self.entries.insert(address.to_string(), (now, health));
+        Ok(health)
+    }
+}
+
+/// Clears the terminal and moves the cursor to its top-left corner.
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Re-renders a frame on every tick, clearing the screen before
+/// each render like `watch(1)`.
+///
+/// The frame is produced by `render` and preceded by `header`.
+/// When `out` is not a terminal, frames are separated by a blank line instead.
+/// Stops once `tick` resolves to `false`.
+pub(crate) async fn watch<W, R, RF, T, TF>(
+    out: &mut W,
+    is_tty: bool,
+    header: &str,
+    mut render: R,
+    mut tick: T,
+) -> Result<()>
+where
+    W: Write,
+    R: FnMut() -> RF,
+    RF: Future<Output = String>,
+    T: FnMut() -> TF,
+    TF: Future<Output = bool>,
+{
+    let mut separator = if is_tty { CLEAR_SCREEN } else { "" };
+    loop {
+        let frame = render().await;
+        write!(out, "{}{}\n\n{}\n", separator, header, frame)?;
+        out.flush()?;
+
+        if !tick().await {
+            return Ok(());
+        }
+        if !is_tty {
+            separator = "\n";
+        }
+    }
+}
+
+/// Returns a warning if a certificate expiring in `days` is within
+/// `warn_days` of its expiry.
+pub(crate) fn certificate_expiry_warning(days: i32, warn_days: u32) -> Option<String> {
+    if days < 0 {
+        Some("Warning: the TLS certificate has expired.".to_string())
+    } else if i64::from(days) <= i64::from(warn_days) {
+        Some(format!(
+            "Warning: the TLS certificate expires in {} days.",
+            days
+        ))
+    } else {
+        None
+    }
+}
+
+/// Time to wait for the HTTPS address of an environment when probing its
+/// TLS certificate, to connect and then for each read or write.
+const CERTIFICATE_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Returns the host of an address, e.g. `example.com` of `example.com:443`.
+fn address_host(address: &str) -> Result<&str> {
+    match address.rsplit_once(':') {
+        Some((host, _)) if !host.is_empty() => Ok(host),
+        _ => bail!("invalid address {}", address),
+    }
+}
+
+/// Returns the days left until the TLS certificate served
+/// on the environment's HTTPS address expires.
+pub(crate) async fn get_certificate_expiry_days(environment: &Environment) -> Result<i32> {
+    let address = environment.environmentd_https_address.clone();
+    mz_ore::task::spawn_blocking(
+        || "certificate_expiry",
+        move || probe_certificate_expiry_days(&address),
+    )
+    .await?
+}
+
+fn probe_certificate_expiry_days(address: &str) -> Result<i32> {
+    let host = address_host(address)?;
+
+    // The certificate is only inspected, not trusted: verifying it would fail
+    // the handshake in the very case of an expired certificate.
+    let mut connector = SslConnector::builder(SslMethod::tls_client())?;
+    connector.set_verify(SslVerifyMode::NONE);
+    let connector = connector.build();
+
+    let socket_address = address
+        .to_socket_addrs()
+        .context("failed to resolve HTTPS address")?
+        .next()
+        .context("failed to resolve HTTPS address")?;
+    let stream = TcpStream::connect_timeout(&socket_address, CERTIFICATE_PROBE_TIMEOUT)
+        .context("failed to connect to HTTPS address")?;
+    stream.set_read_timeout(Some(CERTIFICATE_PROBE_TIMEOUT))?;
+    stream.set_write_timeout(Some(CERTIFICATE_PROBE_TIMEOUT))?;
+    let stream = connector
+        .connect(host, stream)
+        .context("failed to establish TLS connection")?;
+    let certificate = stream
+        .ssl()
+        .peer_certificate()
+        .context("missing TLS certificate")?;
+
+    certificate_expiry_days(&certificate)
+}
+
+/// Returns the days left until a certificate expires.
+/// Negative if the certificate has already expired.
+fn certificate_expiry_days(certificate: &X509Ref) -> Result<i32> {
+    let now = Asn1Time::days_from_now(0)?;
+    let diff = now.diff(certificate.not_after())?;
+
+    Ok(diff.days)
 }
 
 pub(crate) async fn get_provider_by_region_name(
@@ -308,7 +860,7 @@ pub(crate) async fn get_provider_region(
 
     let region = cloud_provider_region_details
         .get(0)
-        .with_context(|| "Region unavailable")?;
+        .ok_or(RegionNotEnabled)?;
 
     Ok(region.to_owned())
 }
@@ -344,3 +896,557 @@ pub(crate) async fn get_provider_region_environment(
 
     Ok(environment)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::time::{Duration, Instant};
+
+    use openssl::asn1::Asn1Time;
+    use openssl::hash::MessageDigest;
+    use openssl::pkey::{PKey, Private};
+    use openssl::rsa::Rsa;
+    use openssl::ssl::{SslAcceptor, SslMethod};
+    use openssl::x509::{X509NameBuilder, X509};
+
+    use std::net::TcpListener;
+    use std::str::FromStr;
+    use std::thread;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use anyhow::anyhow;
+    use reqwest::StatusCode;
+    use serde_json::{json, Value};
+
+    use super::{
+        address_host, certificate_expiry_days, certificate_expiry_warning, debug_http_message,
+        enabled_region, format_connection_details, list_regions_with,
+        probe_certificate_expiry_days, setup_or_rollback, wait_until_healthy,
+        warning_delete_region, watch, CloudProviderRegion, HealthCache, RegionConfig,
+        RegionListing, CLEAR_SCREEN, HEALTH_CACHE_TTL, LIST_REGIONS_CONCURRENCY,
+        WATCH_INTERVAL_SECS,
+    };
+    use crate::utils::SecretPolicy;
+    use crate::{CloudProvider, CloudProviderAndRegion, Environment, Region};
+
+    fn mock_certificate(days: u32) -> X509 {
+        let not_before = Asn1Time::days_from_now(0).unwrap();
+        let not_after = Asn1Time::days_from_now(days).unwrap();
+        mock_certificate_between(&not_before, &not_after).0
+    }
+
+    /// Returns a self-signed certificate valid between the times, and its key.
+    fn mock_certificate_between(
+        not_before: &Asn1Time,
+        not_after: &Asn1Time,
+    ) -> (X509, PKey<Private>) {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", "foo.materialize.cloud")
+            .unwrap();
+        let name = name.build();
+
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(&name).unwrap();
+        builder.set_pubkey(&key).unwrap();
+        builder.set_not_before(not_before).unwrap();
+        builder.set_not_after(not_after).unwrap();
+        builder.sign(&key, MessageDigest::sha256()).unwrap();
+        (builder.build(), key)
+    }
+
+    #[test]
+    fn test_certificate_expiry_warning() {
+        let days = certificate_expiry_days(&mock_certificate(3)).unwrap();
+        assert!(certificate_expiry_warning(days, 7).is_some());
+        assert!(certificate_expiry_warning(days, 1).is_none());
+
+        let days = certificate_expiry_days(&mock_certificate(90)).unwrap();
+        assert!(certificate_expiry_warning(days, 30).is_none());
+
+        assert!(certificate_expiry_warning(-1, 0).is_some());
+    }
+
+    #[test]
+    fn test_address_host() {
+        assert_eq!(
+            address_host("foo.materialize.cloud:443").unwrap(),
+            "foo.materialize.cloud"
+        );
+        assert_eq!(
+            address_host("foo.materialize.cloud:6875").unwrap(),
+            "foo.materialize.cloud"
+        );
+        assert!(address_host("443").is_err());
+        assert!(address_host(":443").is_err());
+    }
+
+    #[test]
+    fn test_probe_expired_certificate() {
+        // A certificate that expired yesterday is still read, so that its
+        // expiry can be reported.
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let day = 24 * 60 * 60;
+        let (certificate, key) = mock_certificate_between(
+            &Asn1Time::from_unix((now - 30 * day).try_into().unwrap()).unwrap(),
+            &Asn1Time::from_unix((now - day).try_into().unwrap()).unwrap(),
+        );
+        let mut acceptor = SslAcceptor::mozilla_intermediate(SslMethod::tls()).unwrap();
+        acceptor.set_private_key(&key).unwrap();
+        acceptor.set_certificate(&certificate).unwrap();
+        let acceptor = acceptor.build();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let _ = acceptor.accept(stream);
+        });
+
+        let days = probe_certificate_expiry_days(&address).unwrap();
+        assert!(days < 0);
+        assert_eq!(
+            certificate_expiry_warning(days, 30).unwrap(),
+            "Warning: the TLS certificate has expired."
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_enabled_region() {
+        let region = enabled_region(
+            StatusCode::OK,
+            json!({"environmentControllerUrl": "https://ec.example.com"}),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(region.environment_controller_url, "https://ec.example.com");
+
+        // Enabling a region twice succeeds.
+        assert!(enabled_region(StatusCode::CONFLICT, Value::Null)
+            .unwrap()
+            .is_none());
+
+        let error = enabled_region(StatusCode::INTERNAL_SERVER_ERROR, Value::Null).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the region controller responded with 500 Internal Server Error"
+        );
+        assert!(enabled_region(StatusCode::OK, json!({})).is_err());
+    }
+
+    #[test]
+    fn test_format_connection_details() {
+        let environment = Environment {
+            environmentd_pgwire_address: "foo.materialize.cloud:6875".to_string(),
+            environmentd_https_address: "foo.materialize.cloud:443".to_string(),
+        };
+        let app_password = format!("mzp_{}", "1".repeat(64));
+        let details = format_connection_details(
+            &environment,
+            "ci+bot@example.com",
+            &app_password,
+            SecretPolicy::Mask,
+        )
+        .unwrap();
+        assert_eq!(
+            details,
+            "SQL address: \tfoo.materialize.cloud:6875\n\
+             HTTPS address: \thttps://foo.materialize.cloud\n\
+             Connect with: \tpsql 'postgres://ci+bot%40example.com@foo.materialize.cloud:6875/materialize?sslmode=require'"
+        );
+
+        let details = format_connection_details(
+            &environment,
+            "ci@example.com",
+            &app_password,
+            SecretPolicy::Reveal,
+        )
+        .unwrap();
+        assert!(details.contains(&format!(
+            "postgres://ci%40example.com:{}@foo.materialize.cloud:6875/",
+            app_password
+        )));
+    }
+
+    #[test]
+    fn test_debug_http_message() {
+        let response = json!({
+            "environmentControllerUrl": "https://ec.materialize.cloud",
+            "accessToken": "eyJ0eXAi",
+            "credentials": [{ "clientSecret": "hunter2" }],
+        });
+        let url = "https://api.materialize.cloud/api/environmentassignment";
+
+        let message = debug_http_message(url, &json!({}), &response, SecretPolicy::Mask);
+        assert!(message.contains(r#""environmentControllerUrl":"https://ec.materialize.cloud""#));
+        assert!(message.contains(r#""accessToken":"<redacted>""#));
+        assert!(message.contains(r#""clientSecret":"<redacted>""#));
+        assert!(!message.contains("eyJ0eXAi"));
+        assert!(!message.contains("hunter2"));
+
+        let message = debug_http_message(url, &json!({}), &response, SecretPolicy::Reveal);
+        assert!(message.contains(r#""accessToken":"eyJ0eXAi""#));
+        assert!(message.contains(r#""clientSecret":"hunter2""#));
+    }
+
+    #[tokio::test]
+    async fn test_watch() {
+        let mut out = Vec::new();
+        let mut healthy = vec![true, false].into_iter();
+        let mut ticks = 0;
+
+        watch(
+            &mut out,
+            true,
+            "Every 5s: mz region status aws/us-east-1",
+            || {
+                let health = if healthy.next().unwrap() { "yes" } else { "no" };
+                async move { format!("Healthy:\t{}", health) }
+            },
+            || {
+                ticks += 1;
+                let more = ticks < 2;
+                async move { more }
+            },
+        )
+        .await
+        .unwrap();
+
+        let header = "Every 5s: mz region status aws/us-east-1";
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!(
+                "{clear}{header}\n\nHealthy:\tyes\n{clear}{header}\n\nHealthy:\tno\n",
+                clear = CLEAR_SCREEN,
+                header = header,
+            )
+        );
+        assert_eq!(ticks, 2);
+
+        let mut out = Vec::new();
+        let mut ticks = 0;
+        watch(
+            &mut out,
+            false,
+            header,
+            || async { "Healthy:\tyes".to_string() },
+            || {
+                ticks += 1;
+                let more = ticks < 2;
+                async move { more }
+            },
+        )
+        .await
+        .unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(!out.contains('\x1b'));
+        assert_eq!(
+            out,
+            format!(
+                "{header}\n\nHealthy:\tyes\n\n{header}\n\nHealthy:\tyes\n",
+                header = header
+            )
+        );
+    }
+
+    #[test]
+    fn test_region_listing() {
+        let cloud_provider = CloudProvider {
+            region: "us-east-1".to_string(),
+            region_controller_url: "https://rc.us-east-1.aws.cloud.materialize.com".to_string(),
+            provider: "aws".to_string(),
+        };
+        let disabled = CloudProviderAndRegion {
+            cloud_provider: cloud_provider.clone(),
+            region: None,
+        };
+        assert_eq!(
+            serde_json::to_value(RegionListing::new(&disabled, None)).unwrap(),
+            json!({
+                "provider": "aws",
+                "region": "us-east-1",
+                "enabled": false,
+                "pgwire_address": null,
+                "https_address": null,
+            })
+        );
+
+        let enabled = CloudProviderAndRegion {
+            cloud_provider,
+            region: Some(Region {
+                environment_controller_url: "https://ec.materialize.cloud:443".to_string(),
+            }),
+        };
+        let environment = Environment {
+            environmentd_pgwire_address: "foo.materialize.cloud:6875".to_string(),
+            environmentd_https_address: "foo.materialize.cloud:443".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(RegionListing::new(&enabled, Some(&environment))).unwrap(),
+            json!({
+                "provider": "aws",
+                "region": "us-east-1",
+                "enabled": true,
+                "pgwire_address": "foo.materialize.cloud:6875",
+                "https_address": "foo.materialize.cloud:443",
+            })
+        );
+    }
+
+    #[test]
+    fn test_region_config() {
+        let config = RegionConfig::parse("region = \"aws/eu-west-1\"\n").unwrap();
+        assert_eq!(config.region, CloudProviderRegion::AwsEuWest1);
+
+        assert!(RegionConfig::parse("region = \"aws/mars-1\"\n").is_err());
+        assert!(RegionConfig::parse("").is_err());
+        assert!(RegionConfig::parse("region = \"aws/eu-west-1\"\nsize = \"xl\"\n").is_err());
+        assert!(RegionConfig::parse("region: aws/eu-west-1\n").is_err());
+    }
+
+    #[test]
+    fn test_health_cache() {
+        let start = Instant::now();
+        let now = Cell::new(start);
+        let probes = Cell::new(0);
+        let probe = || {
+            probes.set(probes.get() + 1);
+            Ok(true)
+        };
+        let mut cache = HealthCache::new(Duration::from_secs(10), || now.get());
+
+        assert!(cache.get("foo.materialize.cloud:6875", probe).unwrap());
+        now.set(start + Duration::from_secs(5));
+        assert!(cache.get("foo.materialize.cloud:6875", probe).unwrap());
+        assert_eq!(probes.get(), 1);
+
+        // Other addresses are probed separately.
+        assert!(cache.get("bar.materialize.cloud:6875", probe).unwrap());
+        assert_eq!(probes.get(), 2);
+
+        now.set(start + Duration::from_secs(10));
+        assert!(cache.get("foo.materialize.cloud:6875", probe).unwrap());
+        assert_eq!(probes.get(), 3);
+
+        let mut cache = HealthCache::new(Duration::ZERO, || now.get());
+        cache.get("foo.materialize.cloud:6875", probe).unwrap();
+        cache.get("foo.materialize.cloud:6875", probe).unwrap();
+        assert_eq!(probes.get(), 5);
+
+        // A watch refreshing at the default interval probes on every refresh.
+        let mut cache = HealthCache::new(HEALTH_CACHE_TTL, || now.get());
+        cache.get("foo.materialize.cloud:6875", probe).unwrap();
+        now.set(now.get() + Duration::from_secs(WATCH_INTERVAL_SECS));
+        cache.get("foo.materialize.cloud:6875", probe).unwrap();
+        assert_eq!(probes.get(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_list_regions_concurrently() {
+        const LATENCY: Duration = Duration::from_millis(200);
+
+        let cloud_providers = (0..2 * LIST_REGIONS_CONCURRENCY)
+            .rev()
+            .map(|i| CloudProvider {
+                region: format!("region-{}", i),
+                region_controller_url: format!("https://rc.region-{}.example.com", i),
+                provider: if i % 2 == 0 { "gcp" } else { "aws" }.to_string(),
+            })
+            .collect::<Vec<_>>();
+        let in_flight = Cell::new(0);
+        let max_in_flight = Cell::new(0);
+        let get_region_details = |cloud_provider: &CloudProvider| {
+            let enabled = cloud_provider.provider == "aws";
+            let url = cloud_provider.region_controller_url.clone();
+            let (in_flight, max_in_flight) = (&in_flight, &max_in_flight);
+            async move {
+                in_flight.set(in_flight.get() + 1);
+                max_in_flight.set(max_in_flight.get().max(in_flight.get()));
+                tokio::time::sleep(LATENCY).await;
+                in_flight.set(in_flight.get() - 1);
+                Ok(if enabled {
+                    vec![Region {
+                        environment_controller_url: url,
+                    }]
+                } else {
+                    vec![]
+                })
+            }
+        };
+
+        // As many providers as the concurrency are all requested at once.
+        let first = &cloud_providers[..LIST_REGIONS_CONCURRENCY];
+        list_regions_with(first, get_region_details).await.unwrap();
+        assert_eq!(max_in_flight.get(), LIST_REGIONS_CONCURRENCY);
+
+        // More providers wait for a slot, in batches.
+        let start = Instant::now();
+        let regions = list_regions_with(&cloud_providers, get_region_details)
+            .await
+            .unwrap();
+        assert!(start.elapsed() >= 2 * LATENCY);
+        assert_eq!(max_in_flight.get(), LIST_REGIONS_CONCURRENCY);
+
+        let listed = regions
+            .iter()
+            .map(|r| {
+                format!(
+                    "{}/{} {}",
+                    r.cloud_provider.provider,
+                    r.cloud_provider.region,
+                    r.region.is_some()
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            listed,
+            [
+                "aws/region-1 true",
+                "aws/region-3 true",
+                "aws/region-5 true",
+                "aws/region-7 true",
+                "gcp/region-0 false",
+                "gcp/region-2 false",
+                "gcp/region-4 false",
+                "gcp/region-6 false",
+            ]
+        );
+
+        let error = list_regions_with(&cloud_providers, |_| async {
+            Err::<Vec<Region>, _>(anyhow!("Unauthorized"))
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(
+            format!("{:#}", error),
+            "Retrieving region details.: Unauthorized"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_healthy() {
+        let interval = Duration::from_millis(1);
+        let mut probes = 0;
+        wait_until_healthy(
+            || {
+                probes += 1;
+                Ok(probes == 3)
+            },
+            interval,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(probes, 3);
+
+        let error = wait_until_healthy(|| Ok(false), interval, Some(Duration::from_millis(20)))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("still provisioning"));
+
+        let error = wait_until_healthy(|| Err(anyhow!("pg_isready")), interval, None)
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "pg_isready");
+    }
+
+    #[tokio::test]
+    async fn test_setup_or_rollback() {
+        let region = CloudProviderRegion::AwsUsEast1;
+        let rollbacks = Cell::new(0);
+        let rollback = |result: anyhow::Result<()>| {
+            let rollbacks = &rollbacks;
+            async move {
+                rollbacks.set(rollbacks.get() + 1);
+                result
+            }
+        };
+
+        // A failure after the region was created deletes it.
+        let error = setup_or_rollback(
+            &region,
+            async { Err::<(), _>(anyhow!("Retrieving environment data.")) },
+            Some(rollback(Ok(()))),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(rollbacks.get(), 1);
+        assert_eq!(
+            format!("{:#}", error),
+            "aws/us-east-1 was created but its setup failed, so it was deleted: \
+             Retrieving environment data."
+        );
+
+        let error = setup_or_rollback(
+            &region,
+            async { Err::<(), _>(anyhow!("Retrieving environment data.")) },
+            Some(rollback(Err(anyhow!("connection refused")))),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(rollbacks.get(), 2);
+        assert!(format!("{:#}", error).contains("deleting it also failed (connection refused)"));
+
+        let error = setup_or_rollback(
+            &region,
+            async { Err::<(), _>(anyhow!("Retrieving environment data.")) },
+            None::<std::future::Ready<anyhow::Result<()>>>,
+        )
+        .await
+        .unwrap_err();
+        assert!(format!("{:#}", error).contains("it was kept (--no-rollback)"));
+
+        // A successful setup keeps the region.
+        setup_or_rollback(&region, async { anyhow::Ok(()) }, Some(rollback(Ok(()))))
+            .await
+            .unwrap();
+        assert_eq!(rollbacks.get(), 2);
+    }
+
+    #[test]
+    fn test_warning_delete_region() {
+        let region = CloudProviderRegion::AwsEuWest1;
+        let mut out = vec![];
+        assert!(
+            warning_delete_region(region, &mut "aws/eu-west-1\n".as_bytes(), &mut out).unwrap()
+        );
+        let warning = String::from_utf8(out).unwrap();
+        assert!(warning.contains("deleting the region aws/eu-west-1 deletes all of its data"));
+
+        for answer in ["", "y\n", "yes\n", "aws/us-east-1\n", "eu-west-1\n"] {
+            assert!(!warning_delete_region(region, &mut answer.as_bytes(), &mut vec![]).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_cloud_provider_region_round_trip() {
+        let regions = CloudProviderRegion::variants()
+            .iter()
+            .map(|variant| {
+                let region = CloudProviderRegion::from_str(variant).unwrap();
+                assert_eq!(region.to_string(), *variant);
+                assert_eq!(
+                    format!("{}/{}", region.provider_name(), region.region_name()),
+                    *variant
+                );
+                region
+            })
+            .collect::<Vec<_>>();
+        // Every variant is listed once.
+        for region in &regions {
+            assert_eq!(regions.iter().filter(|other| *other == region).count(), 1);
+        }
+        assert_eq!(
+            CloudProviderRegion::from_str("aws/ap-southeast-2").unwrap(),
+            CloudProviderRegion::AwsApSoutheast2
+        );
+        assert!(CloudProviderRegion::from_str("aws/us-west-1").is_err());
+    }
+}
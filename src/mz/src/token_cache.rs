@@ -0,0 +1,198 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use openssl::base64::decode_block;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::configuration::FronteggAuth;
+use crate::utils::write_private;
+
+/// Name of the file, next to the configuration file, caching the access tokens.
+const TOKEN_CACHE_FILE_NAME: &str = "tokens.toml";
+
+/// Seconds before its expiry at which a cached access token is refreshed.
+const REFRESH_MARGIN: u64 = 60;
+
+/// Seconds an access token is assumed to be valid for when its expiry
+/// cannot be read from it.
+const FALLBACK_TTL: u64 = 300;
+
+/// Access token of a profile, as cached.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct CachedToken {
+    /// Client ID of the app password the token was issued for, so that the
+    /// token is not reused once the profile's app password changes.
+    client_id: String,
+    access_token: String,
+    /// Seconds since the Unix epoch.
+    expires_at: u64,
+}
+
+/// Access tokens issued for the app passwords of the profiles, keyed by
+/// profile name, so that a command does not need to authenticate again.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct TokenCache {
+    #[serde(default)]
+    tokens: BTreeMap<String, CachedToken>,
+}
+
+impl TokenCache {
+    fn path(config_path: &Path) -> PathBuf {
+        config_path.with_file_name(TOKEN_CACHE_FILE_NAME)
+    }
+
+    /// Loads the cache next to the configuration file. A missing or
+    /// unreadable cache is empty.
+    pub(crate) fn load(config_path: &Path) -> TokenCache {
+        fs::read_to_string(Self::path(config_path))
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn save(&self, config_path: &Path) -> Result<()> {
+        let contents = toml::to_string_pretty(self).context("failed to write out token cache")?;
+        write_private(&Self::path(config_path), &contents)
+            .context("failed to write out token cache")
+    }
+
+    /// Returns the cached token of the profile, unless it was issued for
+    /// another app password or expires within the refresh margin.
+    pub(crate) fn get(&self, profile: &str, client_id: &str, now: u64) -> Option<FronteggAuth> {
+        self.tokens
+            .get(profile)
+            .filter(|token| token.client_id == client_id)
+            .filter(|token| token.expires_at > now.saturating_add(REFRESH_MARGIN))
+            .map(|token| FronteggAuth {
+                access_token: token.access_token.clone(),
+            })
+    }
+
+    pub(crate) fn insert(&mut self, profile: &str, client_id: &str, auth: &FronteggAuth, now: u64) {
+        let expires_at =
+            token_expiry(&auth.access_token).unwrap_or_else(|| now.saturating_add(FALLBACK_TTL));
+        self.tokens.insert(
+            profile.to_string(),
+            CachedToken {
+                client_id: client_id.to_string(),
+                access_token: auth.access_token.clone(),
+                expires_at,
+            },
+        );
+    }
+
+    /// Removes the tokens of the profiles, returning whether any was cached.
+    pub(crate) fn remove(&mut self, profiles: &[String]) -> bool {
+        let mut removed = false;
+        for profile in profiles {
+            removed |= self.tokens.remove(profile).is_some();
+        }
+        removed
+    }
+}
+
+/// Returns the current time, in seconds since the Unix epoch.
+pub(crate) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+/// Decodes the claims of a JWT.
+///
+/// The signature is not verified: the claims are only used for
+/// bookkeeping, such as when to refresh the token.
+pub(crate) fn token_claims<T: DeserializeOwned>(access_token: &str) -> Option<T> {
+    // The payload is base64url encoded, without padding.
+    let payload = access_token.split('.').nth(1)?;
+    let mut payload = payload.replace('-', "+").replace('_', "/");
+    while payload.len() % 4 != 0 {
+        payload.push('=');
+    }
+    let claims = decode_block(&payload).ok()?;
+    serde_json::from_slice(&claims).ok()
+}
+
+/// Reads the `exp` claim of a JWT, in seconds since the Unix epoch.
+fn token_expiry(access_token: &str) -> Option<u64> {
+    #[derive(Deserialize)]
+    struct Claims {
+        exp: u64,
+    }
+
+    token_claims::<Claims>(access_token).map(|claims| claims.exp)
+}
+
+#[cfg(test)]
+mod tests {
+    use openssl::base64::encode_block;
+
+    use super::{token_expiry, TokenCache, FALLBACK_TTL};
+    use crate::configuration::FronteggAuth;
+
+    fn jwt(payload: &str) -> String {
+        let payload = encode_block(payload.as_bytes())
+            .trim_end_matches('=')
+            .replace('+', "-")
+            .replace('/', "_");
+        format!("eyJhbGciOiJSUzI1NiJ9.{}.c2lnbmF0dXJl", payload)
+    }
+
+    #[test]
+    fn test_token_expiry() {
+        assert_eq!(
+            token_expiry(&jwt(r#"{"sub":"ci@example.com","exp":1700000000}"#)),
+            Some(1700000000)
+        );
+        assert_eq!(token_expiry(&jwt(r#"{"sub":"ci@example.com"}"#)), None);
+        assert_eq!(token_expiry("opaque"), None);
+        assert_eq!(token_expiry("a.!!!.c"), None);
+    }
+
+    #[test]
+    fn test_token_cache() {
+        let auth = FronteggAuth {
+            access_token: jwt(r#"{"exp":1000}"#),
+        };
+        let mut cache = TokenCache::default();
+        cache.insert("default", "client", &auth, 500);
+
+        let cached = cache.get("default", "client", 500).unwrap();
+        assert_eq!(cached.access_token, auth.access_token);
+        // Refreshed ahead of its expiry, and never reused for another
+        // profile or app password.
+        assert!(cache.get("default", "client", 950).is_none());
+        assert!(cache.get("default", "other", 500).is_none());
+        assert!(cache.get("staging", "client", 500).is_none());
+
+        let opaque = FronteggAuth {
+            access_token: "opaque".to_string(),
+        };
+        cache.insert("staging", "client", &opaque, 500);
+        assert!(cache.get("staging", "client", 500).is_some());
+        assert!(cache.get("staging", "client", 500 + FALLBACK_TTL).is_none());
+
+        let round_trip: TokenCache =
+            toml::from_str(&toml::to_string_pretty(&cache).unwrap()).unwrap();
+        assert!(round_trip.get("default", "client", 500).is_some());
+
+        assert!(cache.remove(&["default".to_string(), "missing".to_string()]));
+        assert!(!cache.remove(&["default".to_string()]));
+        assert!(cache.get("default", "client", 500).is_none());
+    }
+}
@@ -7,8 +7,426 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use anyhow::{ensure, Context, Result};
+use atty::Stream;
+use axum::http;
+use clap::ArgEnum;
 use indicatif::{ProgressBar, ProgressStyle};
-use std::time::Duration;
+use openssl::x509::X509;
+use reqwest::{Certificate, Client, RequestBuilder, Response};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::env;
+use std::ffi::OsString;
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::future::Future;
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{self, exit, Command, Stdio};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use terminal_size::{terminal_size, Height};
+
+/// Exit code of a command aborted by `--deadline`, as used by `timeout(1)`.
+pub(crate) const DEADLINE_EXIT_CODE: i32 = 124;
+
+/// Error of a command that ran past its deadline.
+#[derive(Debug)]
+pub(crate) struct DeadlineExceeded(Duration);
+
+impl Display for DeadlineExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "command timed out after {} seconds", self.0.as_secs())
+    }
+}
+
+impl std::error::Error for DeadlineExceeded {}
+
+/// Exit code of a command interrupted with Ctrl-C, as shells report a SIGINT.
+pub(crate) const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// Error of a command interrupted with Ctrl-C.
+#[derive(Debug)]
+pub(crate) struct Interrupted;
+
+impl Display for Interrupted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "command interrupted")
+    }
+}
+
+impl std::error::Error for Interrupted {}
+
+/// Exit code of `mz region status` for an enabled region failing its health check.
+pub(crate) const UNHEALTHY_EXIT_CODE: i32 = 3;
+
+/// Error of a region failing its health check.
+#[derive(Debug)]
+pub(crate) struct RegionUnhealthy;
+
+impl Display for RegionUnhealthy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "region is not healthy")
+    }
+}
+
+impl std::error::Error for RegionUnhealthy {}
+
+/// Exit code of a command on a region that is not enabled.
+pub(crate) const NOT_ENABLED_EXIT_CODE: i32 = 4;
+
+/// Error of a command on a region that is not enabled.
+#[derive(Debug)]
+pub(crate) struct RegionNotEnabled;
+
+impl Display for RegionNotEnabled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Region unavailable")
+    }
+}
+
+impl std::error::Error for RegionNotEnabled {}
+
+/// Completes on the first Ctrl-C. Any further Ctrl-C exits right away,
+/// without waiting for the interrupted command to clean up.
+pub(crate) async fn ctrl_c() {
+    if tokio::signal::ctrl_c().await.is_err() {
+        // Without a signal handler, Ctrl-C keeps its default behavior.
+        return std::future::pending().await;
+    }
+    mz_ore::task::spawn(|| "second_ctrl_c", async {
+        let _ = tokio::signal::ctrl_c().await;
+        exit(INTERRUPTED_EXIT_CODE)
+    });
+}
+
+/// Runs the command until it completes, or until the interrupt, if any,
+/// does. The command is then dropped, which aborts its in-flight requests.
+pub(crate) async fn with_interrupt<F, I, T>(interrupt: Option<I>, command: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+    I: Future<Output = ()>,
+{
+    match interrupt {
+        Some(interrupt) => tokio::select! {
+            biased;
+            result = command => result,
+            () = interrupt => Err(Interrupted.into()),
+        },
+        None => command.await,
+    }
+}
+
+/// Runs the command, aborting it once the deadline, if any, passes.
+pub(crate) async fn with_deadline<F, T>(deadline: Option<Duration>, command: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    match deadline {
+        Some(deadline) => tokio::time::timeout(deadline, command)
+            .await
+            .map_err(|_| DeadlineExceeded(deadline))?,
+        None => command.await,
+    }
+}
+
+/// Environment variable pointing to a PEM bundle of additional CA certificates.
+const CA_BUNDLE_VAR: &str = "MZ_CA_BUNDLE";
+
+/// Warning printed when TLS certificates are not verified.
+pub(crate) const INSECURE_WARNING: &str = "WARNING: --insecure is set, TLS certificates are not \
+    verified and the connections can be intercepted. Never use it against production.";
+
+/// Builds the HTTP client of a command.
+///
+/// Requests go through the proxies of the `HTTP_PROXY`, `HTTPS_PROXY` and
+/// `NO_PROXY` environment variables, if set. The certificates of
+/// `MZ_CA_BUNDLE` are trusted on top of the system ones, and `insecure`
+/// accepts any certificate.
+pub(crate) fn http_client(insecure: bool) -> Result<Client> {
+    let ca_bundle = env::var_os(CA_BUNDLE_VAR).map(PathBuf::from);
+    http_client_with(insecure, ca_bundle.as_deref())
+}
+
+fn http_client_with(insecure: bool, ca_bundle: Option<&Path>) -> Result<Client> {
+    let mut builder = Client::builder().danger_accept_invalid_certs(insecure);
+    if let Some(path) = ca_bundle {
+        let bundle = fs::read(path)
+            .with_context(|| format!("failed to read the CA bundle {}", path.display()))?;
+        let certificates = X509::stack_from_pem(&bundle)
+            .ok()
+            .filter(|certificates| !certificates.is_empty())
+            .with_context(|| format!("failed to parse the CA bundle {}", path.display()))?;
+        for certificate in certificates {
+            builder = builder.add_root_certificate(Certificate::from_der(&certificate.to_der()?)?);
+        }
+    }
+
+    builder.build().context("failed to build the HTTP client")
+}
+
+/// Environment variable setting how many times an HTTP request is attempted.
+const HTTP_RETRIES_VAR: &str = "MZ_HTTP_RETRIES";
+
+/// Attempts of an HTTP request when `MZ_HTTP_RETRIES` is not set.
+const DEFAULT_HTTP_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry, doubled before each of the following ones.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+
+/// Returns how many times an HTTP request is attempted, at least once.
+fn http_attempts() -> u32 {
+    env::var(HTTP_RETRIES_VAR)
+        .ok()
+        .and_then(|attempts| attempts.parse::<u32>().ok())
+        .unwrap_or(DEFAULT_HTTP_ATTEMPTS)
+        .max(1)
+}
+
+/// Returns the delay before the retry following the attempt, doubling the
+/// base delay after every attempt and adding up to half of it again, so that
+/// concurrent clients do not retry in lockstep.
+fn retry_delay(base_delay: Duration, attempt: u32) -> Duration {
+    let delay = base_delay.saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+    let jitter = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |now| now.subsec_nanos());
+    delay + delay.mul_f64(f64::from(jitter % 1000) / 2000.0)
+}
+
+/// Runs the attempt until it succeeds, fails with a permanent error, or
+/// runs out of attempts, sleeping between each of them.
+///
+/// Returns the outcome of the last attempt, and the number of attempts made.
+pub(crate) async fn retry<F, Fut, T>(
+    attempts: u32,
+    base_delay: Duration,
+    mut attempt: F,
+    is_transient: impl Fn(&T) -> bool,
+) -> (T, u32)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = T>,
+{
+    let mut made = 1;
+    loop {
+        let outcome = attempt().await;
+        if made >= attempts || !is_transient(&outcome) {
+            return (outcome, made);
+        }
+        tokio::time::sleep(retry_delay(base_delay, made)).await;
+        made += 1;
+    }
+}
+
+/// How much of the HTTP traffic is logged to stderr, as set by `--verbose`:
+/// nothing, the requests and the statuses of their responses, or also the
+/// bodies of the responses.
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+pub(crate) fn set_verbosity(verbosity: u8) {
+    VERBOSITY.store(verbosity, Ordering::Relaxed);
+}
+
+/// Logs the request about to be sent, when verbose.
+fn log_request(request: &RequestBuilder, verbosity: u8) {
+    if verbosity == 0 {
+        return;
+    }
+    // A request with a streamed body cannot be cloned, and is not logged.
+    if let Some(Ok(request)) = request.try_clone().map(RequestBuilder::build) {
+        eprintln!("> {} {}", request.method(), request.url());
+    }
+}
+
+/// Logs the response to a request, when verbose, and its body, with its
+/// secrets masked, when very verbose.
+async fn log_response(
+    outcome: reqwest::Result<Response>,
+    verbosity: u8,
+) -> reqwest::Result<Response> {
+    let response = match outcome {
+        Ok(response) if verbosity > 0 => response,
+        Err(error) if verbosity > 0 => {
+            eprintln!("< {}", error);
+            return Err(error);
+        }
+        outcome => return outcome,
+    };
+    eprintln!("< {}", response.status());
+    if verbosity < 2 {
+        return Ok(response);
+    }
+
+    // Reading the body consumes the response, which is then rebuilt around it.
+    let mut rebuilt = http::Response::builder()
+        .status(response.status())
+        .version(response.version());
+    for (name, value) in response.headers() {
+        rebuilt = rebuilt.header(name, value);
+    }
+    let body = response.bytes().await?;
+    eprintln!("< {}", redacted_body(&body));
+    Ok(Response::from(
+        rebuilt
+            .body(body)
+            .expect("the parts of a received response are valid"),
+    ))
+}
+
+/// Formats a response body for the log, masking the secrets of a JSON body,
+/// and the app passwords of any other.
+fn redacted_body(body: &[u8]) -> String {
+    match serde_json::from_slice::<Value>(body) {
+        Ok(mut value) => {
+            redact_secrets(&mut value, SecretPolicy::Mask);
+            value.to_string()
+        }
+        Err(_) => SecretPolicy::Mask.text(&String::from_utf8_lossy(body)),
+    }
+}
+
+/// Sends the request, retrying on connection errors and server errors, but
+/// never on client errors, up to `MZ_HTTP_RETRIES` times.
+///
+/// The request is built anew for every attempt, and logged per `--verbose`.
+pub(crate) async fn send_with_retries<F>(mut request: F) -> Result<Response>
+where
+    F: FnMut() -> RequestBuilder,
+{
+    let verbosity = VERBOSITY.load(Ordering::Relaxed);
+    let (outcome, attempts) = retry(
+        http_attempts(),
+        RETRY_BASE_DELAY,
+        || {
+            let request = request();
+            log_request(&request, verbosity);
+            async move { log_response(request.send().await, verbosity).await }
+        },
+        |outcome| match outcome {
+            Ok(response) => response.status().is_server_error(),
+            Err(error) => error.is_connect() || error.is_timeout(),
+        },
+    )
+    .await;
+    let response =
+        outcome.with_context(|| format!("request failed after {} attempts", attempts))?;
+    ensure!(
+        !response.status().is_server_error(),
+        "the server responded with {} after {} attempts",
+        response.status(),
+        attempts
+    );
+    Ok(response)
+}
+
+/// Applies the secret policy to the values of the fields that may hold secrets, at any depth.
+pub(crate) fn redact_secrets(value: &mut Value, secret_policy: SecretPolicy) {
+    match value {
+        Value::Object(fields) => {
+            for (name, field) in fields.iter_mut() {
+                let name = name.to_lowercase();
+                if ["token", "secret", "password", "key", "authorization"]
+                    .iter()
+                    .any(|secret| name.contains(secret))
+                {
+                    if let Value::String(secret) = field {
+                        *secret = secret_policy.secret(secret).to_string();
+                    } else if secret_policy == SecretPolicy::Mask {
+                        *field = Value::String(REDACTED.to_string());
+                    }
+                } else {
+                    redact_secrets(field, secret_policy);
+                }
+            }
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| redact_secrets(value, secret_policy)),
+        _ => (),
+    }
+}
+
+/// Placeholder printed instead of a secret.
+pub(crate) const REDACTED: &str = "<redacted>";
+
+/// Whether credential material may be printed.
+///
+/// Every command consults the policy before printing a secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SecretPolicy {
+    Mask,
+    Reveal,
+}
+
+impl SecretPolicy {
+    pub(crate) fn new(reveal_secrets: bool) -> SecretPolicy {
+        if reveal_secrets {
+            SecretPolicy::Reveal
+        } else {
+            SecretPolicy::Mask
+        }
+    }
+
+    /// Returns the secret, or a placeholder if secrets are masked.
+    pub(crate) fn secret<'a>(&self, secret: &'a str) -> &'a str {
+        match self {
+            SecretPolicy::Mask => REDACTED,
+            SecretPolicy::Reveal => secret,
+        }
+    }
+
+    /// Masks the app passwords appearing in free-form text, e.g. in an error message.
+    pub(crate) fn text(&self, text: &str) -> String {
+        const PREFIX: &str = "mzp_";
+        const LENGTH: usize = 68;
+
+        if *self == SecretPolicy::Reveal {
+            return text.to_string();
+        }
+
+        let mut masked = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find(PREFIX) {
+            let candidate = &rest[start..];
+            let is_app_password = candidate.len() >= LENGTH
+                && candidate.as_bytes()[PREFIX.len()..LENGTH]
+                    .iter()
+                    .all(u8::is_ascii_hexdigit);
+            if is_app_password {
+                masked.push_str(&rest[..start]);
+                masked.push_str(REDACTED);
+                rest = &candidate[LENGTH..];
+            } else {
+                masked.push_str(&rest[..start + PREFIX.len()]);
+                rest = &candidate[PREFIX.len()..];
+            }
+        }
+        masked.push_str(rest);
+        masked
+    }
+}
+
+/// Pager used when `$PAGER` is not set.
+const DEFAULT_PAGER: &str = "less -FRX";
+
+/// Output formats supported by the CLI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ArgEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// Error as reported when using the JSON format.
+#[derive(Serialize)]
+struct ErrorOutput {
+    error: String,
+    code: i32,
+}
 
 /// Trim lines. Useful when reading input data.
 pub(crate) fn trim_newline(s: &mut String) {
@@ -20,8 +438,32 @@ pub(crate) fn trim_newline(s: &mut String) {
     }
 }
 
+/// Environment variable hiding the loading spinners, even on a terminal,
+/// when set to a non-empty value.
+const NO_SPINNER_VAR: &str = "MZ_NO_SPINNER";
+
 /// Print a loading spinner with a particular message til finished.
-pub(crate) fn run_loading_spinner(message: String) -> ProgressBar {
+///
+/// The spinner is hidden when stderr is not a terminal,
+/// e.g. when redirected to a file, when using the JSON format,
+/// and when `MZ_NO_SPINNER` is set.
+pub(crate) fn run_loading_spinner(message: String, format: OutputFormat) -> ProgressBar {
+    let no_spinner = env::var_os(NO_SPINNER_VAR).map_or(false, |value| !value.is_empty());
+    loading_spinner(
+        message,
+        show_spinner(format, atty::is(Stream::Stderr), no_spinner),
+    )
+}
+
+fn show_spinner(format: OutputFormat, is_tty: bool, no_spinner: bool) -> bool {
+    format == OutputFormat::Text && is_tty && !no_spinner
+}
+
+fn loading_spinner(message: String, is_tty: bool) -> ProgressBar {
+    if !is_tty {
+        return ProgressBar::hidden();
+    }
+
     let progress_bar = ProgressBar::new_spinner();
     progress_bar.enable_steady_tick(Duration::from_millis(120));
     progress_bar.set_style(
@@ -37,3 +479,437 @@ pub(crate) fn run_loading_spinner(message: String) -> ProgressBar {
 
     progress_bar
 }
+
+/// Stops the spinner, replacing it with the message.
+/// Prints the message as plain text if the spinner is hidden.
+pub(crate) fn finish_loading_spinner(progress_bar: ProgressBar, message: &'static str) {
+    if progress_bar.is_hidden() {
+        println!("{}", message);
+    } else {
+        progress_bar.finish_with_message(message);
+    }
+}
+
+/// Writes a file holding secrets, readable only by its owner on Unix.
+///
+/// The contents are written to a temporary file in the same directory,
+/// then renamed over the file, so that a crash never leaves it half-written.
+pub(crate) fn write_private(path: &Path, contents: &str) -> Result<()> {
+    let file_name = path
+        .file_name()
+        .with_context(|| format!("invalid file path {}", path.display()))?;
+    let mut temp_name = OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(format!(".{}.tmp", process::id()));
+    let temp_path = path.with_file_name(temp_name);
+
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+
+    let result = options.open(&temp_path).and_then(|mut file| {
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)
+    });
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result.with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Runs a pager command, writing the output to its standard input.
+fn spawn_pager(pager: &str, output: &str) -> Result<()> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(pager)
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to spawn the pager {}", pager))?;
+
+    // The pager may exit before reading all of its input, e.g. when
+    // the user quits early, so errors writing to it are ignored.
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = writeln!(stdin, "{}", output);
+    }
+    child.wait().context("failed to wait for the pager")?;
+
+    Ok(())
+}
+
+/// Prints the output, through a pager when stdout is a terminal
+/// and the output does not fit in it.
+fn print_paged_with<F>(
+    output: &str,
+    no_pager: bool,
+    is_tty: bool,
+    terminal_height: Option<usize>,
+    spawn: F,
+) -> Result<()>
+where
+    F: FnOnce(&str, &str) -> Result<()>,
+{
+    let exceeds_screen = terminal_height.map_or(false, |height| output.lines().count() >= height);
+
+    if output.is_empty() {
+        Ok(())
+    } else if !no_pager && is_tty && exceeds_screen {
+        let pager = env::var("PAGER").unwrap_or_else(|_| DEFAULT_PAGER.to_string());
+        spawn(&pager, output)
+    } else {
+        println!("{}", output);
+        Ok(())
+    }
+}
+
+/// Prints a listing, through `$PAGER` if it is longer than the terminal,
+/// unless paging is disabled.
+pub(crate) fn print_paged(output: &str, no_pager: bool) -> Result<()> {
+    let terminal_height = terminal_size().map(|(_, Height(height))| usize::from(height));
+    print_paged_with(
+        output,
+        no_pager,
+        atty::is(Stream::Stdout),
+        terminal_height,
+        spawn_pager,
+    )
+}
+
+/// Format an error message in a particular output format.
+fn fail_message(
+    format: OutputFormat,
+    secret_policy: SecretPolicy,
+    error: &anyhow::Error,
+    code: i32,
+) -> String {
+    match format {
+        OutputFormat::Text => secret_policy.text(&format!("Error: {:?}", error)),
+        OutputFormat::Json => serde_json::to_string(&ErrorOutput {
+            error: secret_policy.text(&format!("{:#}", error)),
+            code,
+        })
+        .expect("serializing a string and an integer cannot fail"),
+    }
+}
+
+/// Returns the exit code for the error.
+pub(crate) fn exit_code(error: &anyhow::Error) -> i32 {
+    if error.is::<DeadlineExceeded>() {
+        DEADLINE_EXIT_CODE
+    } else if error.is::<Interrupted>() {
+        INTERRUPTED_EXIT_CODE
+    } else if error.is::<RegionUnhealthy>() {
+        UNHEALTHY_EXIT_CODE
+    } else if error.is::<RegionNotEnabled>() {
+        NOT_ENABLED_EXIT_CODE
+    } else {
+        1
+    }
+}
+
+/// Print an error to stderr in a particular output format and exit.
+pub(crate) fn exit_with_fail_message(
+    format: OutputFormat,
+    secret_policy: SecretPolicy,
+    error: &anyhow::Error,
+) -> ! {
+    let code = exit_code(error);
+    eprintln!("{}", fail_message(format, secret_policy, error, code));
+    exit(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use tokio::sync::oneshot;
+
+    use std::fs;
+
+    use openssl::asn1::Asn1Time;
+    use openssl::hash::MessageDigest;
+    use openssl::pkey::PKey;
+    use openssl::rsa::Rsa;
+    use openssl::x509::{X509NameBuilder, X509};
+
+    use super::{
+        exit_code, fail_message, http, http_client_with, loading_spinner, log_response,
+        print_paged_with, redacted_body, retry, retry_delay, show_spinner, with_deadline,
+        with_interrupt, OutputFormat, RegionNotEnabled, RegionUnhealthy, Response, SecretPolicy,
+        DEADLINE_EXIT_CODE, INTERRUPTED_EXIT_CODE, NOT_ENABLED_EXIT_CODE, UNHEALTHY_EXIT_CODE,
+    };
+
+    #[test]
+    fn test_fail_message_json() {
+        let error = anyhow!("Unauthorized").context("failed to validate profile");
+        let message = fail_message(OutputFormat::Json, SecretPolicy::Mask, &error, 1);
+        let value: serde_json::Value = serde_json::from_str(&message).unwrap();
+
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "error": "failed to validate profile: Unauthorized",
+                "code": 1,
+            })
+        );
+    }
+
+    #[test]
+    fn test_print_paged() {
+        let long_output = vec!["aws/us-east-1  enabled"; 50].join("\n");
+
+        let mut spawned = false;
+        print_paged_with(&long_output, false, true, Some(24), |_, _| {
+            spawned = true;
+            Ok(())
+        })
+        .unwrap();
+        assert!(spawned);
+
+        let cases = [
+            ("aws/us-east-1  enabled", false, true),
+            (long_output.as_str(), true, true),
+            (long_output.as_str(), false, false),
+        ];
+        for (output, no_pager, is_tty) in cases {
+            print_paged_with(output, no_pager, is_tty, Some(24), |_, _| {
+                panic!("the pager should not be spawned")
+            })
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_secret_policy() {
+        let app_password = format!("mzp_{}", "0123456789abcdef".repeat(4));
+        let error = anyhow!("invalid app password {}", app_password);
+
+        let message = fail_message(OutputFormat::Text, SecretPolicy::Mask, &error, 1);
+        assert!(message.starts_with("Error: invalid app password <redacted>"));
+        assert!(!message.contains(&app_password));
+        assert_eq!(SecretPolicy::Mask.secret(&app_password), "<redacted>");
+
+        let message = fail_message(OutputFormat::Text, SecretPolicy::Reveal, &error, 1);
+        assert!(message.contains(&app_password));
+        assert_eq!(SecretPolicy::Reveal.secret(&app_password), app_password);
+
+        // Text that only looks like the start of an app password is kept.
+        assert_eq!(
+            SecretPolicy::Mask.text("mzp_ and mzp_12"),
+            "mzp_ and mzp_12"
+        );
+    }
+
+    #[test]
+    fn test_loading_spinner_redirected() {
+        let progress_bar = loading_spinner("Enabling region...".to_string(), false);
+        assert!(progress_bar.is_hidden());
+        progress_bar.finish_with_message("Region enabled.");
+    }
+
+    #[test]
+    fn test_redacted_body() {
+        assert_eq!(
+            redacted_body(br#"{"accessToken":"eyJ","expiresIn":86400}"#),
+            r#"{"accessToken":"<redacted>","expiresIn":86400}"#
+        );
+        let password = format!("mzp_{}", "1".repeat(64));
+        assert_eq!(
+            redacted_body(format!("invalid app password {}", password).as_bytes()),
+            "invalid app password <redacted>"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_log_response() {
+        let response = || {
+            Response::from(
+                http::Response::builder()
+                    .status(201)
+                    .body(r#"{"clientId":"ci","secret":"hunter2"}"#)
+                    .unwrap(),
+            )
+        };
+        for verbosity in 0..=2 {
+            let logged = log_response(Ok(response()), verbosity).await.unwrap();
+            assert_eq!(logged.status(), 201);
+            // The body is still readable once logged.
+            assert_eq!(
+                logged.text().await.unwrap(),
+                r#"{"clientId":"ci","secret":"hunter2"}"#
+            );
+        }
+    }
+
+    #[test]
+    fn test_show_spinner() {
+        assert!(show_spinner(OutputFormat::Text, true, false));
+        assert!(!show_spinner(OutputFormat::Text, false, false));
+        assert!(!show_spinner(OutputFormat::Text, true, true));
+        assert!(!show_spinner(OutputFormat::Json, true, false));
+    }
+
+    #[tokio::test]
+    async fn test_with_deadline() {
+        let error = with_deadline(Some(Duration::from_millis(10)), async {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            Ok(())
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(error.to_string(), "command timed out after 0 seconds");
+        assert_eq!(exit_code(&error), DEADLINE_EXIT_CODE);
+
+        let result = with_deadline(Some(Duration::from_secs(10)), async { Ok(1) }).await;
+        assert_eq!(result.unwrap(), 1);
+        let error = with_deadline(None, async { Err::<(), _>(anyhow!("Unauthorized")) })
+            .await
+            .unwrap_err();
+        assert_eq!(exit_code(&error), 1);
+    }
+
+    #[test]
+    fn test_region_exit_codes() {
+        let error = anyhow::Error::new(RegionNotEnabled)
+            .context("Retrieving region data.")
+            .context("Retrieving cloud provider region.");
+        assert_eq!(exit_code(&error), NOT_ENABLED_EXIT_CODE);
+        assert_eq!(
+            exit_code(&anyhow::Error::new(RegionUnhealthy)),
+            UNHEALTHY_EXIT_CODE
+        );
+        assert_ne!(UNHEALTHY_EXIT_CODE, NOT_ENABLED_EXIT_CODE);
+    }
+
+    #[tokio::test]
+    async fn test_with_interrupt() {
+        /// Records that the command was dropped, cancelling it.
+        struct Cancelled(Arc<AtomicBool>);
+
+        impl Drop for Cancelled {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let (interrupt, interrupted) = oneshot::channel::<()>();
+        let command = async {
+            let _cancelled = Cancelled(Arc::clone(&cancelled));
+            std::future::pending::<()>().await;
+            Ok(())
+        };
+        interrupt.send(()).unwrap();
+        let error = with_interrupt(
+            Some(async {
+                let _ = interrupted.await;
+            }),
+            command,
+        )
+        .await
+        .unwrap_err();
+        assert!(cancelled.load(Ordering::SeqCst));
+        assert_eq!(error.to_string(), "command interrupted");
+        assert_eq!(exit_code(&error), INTERRUPTED_EXIT_CODE);
+
+        // A command that completes is never interrupted.
+        let result = with_interrupt(Some(std::future::pending()), async { Ok(1) }).await;
+        assert_eq!(result.unwrap(), 1);
+        let result = with_interrupt(None::<std::future::Pending<()>>, async { Ok(2) }).await;
+        assert_eq!(result.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry() {
+        // Server errors are retried until a response succeeds.
+        let mut statuses = vec![200, 502, 503].into_iter().rev();
+        let (status, attempts) = retry(
+            3,
+            Duration::ZERO,
+            || {
+                let status = statuses.next();
+                async move { status }
+            },
+            |status| status.map_or(false, |status| status >= 500),
+        )
+        .await;
+        assert_eq!((status, attempts), (Some(200), 3));
+
+        // Client errors are not.
+        let mut calls = 0;
+        let (status, attempts) = retry(
+            3,
+            Duration::ZERO,
+            || {
+                calls += 1;
+                async { 404 }
+            },
+            |status| *status >= 500,
+        )
+        .await;
+        assert_eq!((status, attempts, calls), (404, 1, 1));
+
+        // The last error is returned once the attempts run out.
+        let (status, attempts) = retry(2, Duration::ZERO, || async { 502 }, |s| *s >= 500).await;
+        assert_eq!((status, attempts), (502, 2));
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let base = Duration::from_millis(100);
+        for (attempt, delay) in [(1, 100), (2, 200), (3, 400)] {
+            let delay = Duration::from_millis(delay);
+            let jittered = retry_delay(base, attempt);
+            assert!(
+                jittered >= delay && jittered <= delay * 3 / 2,
+                "{:?}",
+                jittered
+            );
+        }
+    }
+
+    fn ca_certificate(name: &str) -> Vec<u8> {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let mut subject = X509NameBuilder::new().unwrap();
+        subject.append_entry_by_text("CN", name).unwrap();
+        let subject = subject.build();
+
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_subject_name(&subject).unwrap();
+        builder.set_issuer_name(&subject).unwrap();
+        builder.set_pubkey(&key).unwrap();
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(30).unwrap())
+            .unwrap();
+        builder.sign(&key, MessageDigest::sha256()).unwrap();
+        builder.build().to_pem().unwrap()
+    }
+
+    #[test]
+    fn test_http_client_ca_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ca-bundle.pem");
+        let mut bundle = ca_certificate("Staging CA");
+        bundle.extend(ca_certificate("Proxy CA"));
+        fs::write(&path, bundle).unwrap();
+
+        let trusted = http_client_with(false, Some(&path));
+        fs::write(&path, "not a certificate").unwrap();
+        let malformed = http_client_with(false, Some(&path));
+        let missing = http_client_with(true, Some(&dir.path().join("missing.pem")));
+
+        assert!(trusted.is_ok());
+        assert!(format!("{:#}", malformed.unwrap_err()).contains("failed to parse the CA bundle"));
+        assert!(format!("{:#}", missing.unwrap_err()).contains("failed to read the CA bundle"));
+        assert!(http_client_with(true, None).is_ok());
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::Result;
+use mz_build_info::{build_info, BuildInfo};
+use serde::Serialize;
+
+use crate::utils::OutputFormat;
+
+/// Build of the CLI.
+pub(crate) const BUILD_INFO: BuildInfo = build_info!();
+
+/// Version of the CLI, with the metadata of its build.
+#[derive(Debug, Serialize)]
+pub(crate) struct Version {
+    version: &'static str,
+    git_sha: &'static str,
+    build_date: &'static str,
+}
+
+/// Version of this build.
+pub(crate) const VERSION: Version = Version {
+    version: BUILD_INFO.version,
+    git_sha: BUILD_INFO.sha,
+    build_date: BUILD_INFO.time,
+};
+
+impl Version {
+    /// Formats the version as a single line, e.g.:
+    /// `mz 0.1.0 (0123456789abcdef0123456789abcdef01234567 2026-10-16T12:00:00Z)`
+    pub(crate) fn format(&self, format: OutputFormat) -> Result<String> {
+        Ok(match format {
+            OutputFormat::Text => {
+                format!("mz {} ({} {})", self.version, self.git_sha, self.build_date)
+            }
+            OutputFormat::Json => serde_json::to_string(self)?,
+        })
+    }
+
+    /// Describes the version with a line per field, as printed by `mz version`.
+    pub(crate) fn describe(&self, format: OutputFormat) -> Result<String> {
+        Ok(match format {
+            OutputFormat::Text => format!(
+                "mz {}\ngit sha:    {}\nbuild date: {}",
+                self.version, self.git_sha, self.build_date
+            ),
+            OutputFormat::Json => self.format(format)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::{BUILD_INFO, VERSION};
+    use crate::utils::OutputFormat;
+
+    #[test]
+    fn test_version_format() {
+        let json = VERSION.format(OutputFormat::Json).unwrap();
+        let json = serde_json::from_str::<Value>(&json).unwrap();
+        for field in ["version", "git_sha", "build_date"] {
+            assert!(
+                json[field]
+                    .as_str()
+                    .map_or(false, |value| !value.is_empty()),
+                "missing {} in {}",
+                field,
+                json
+            );
+        }
+        assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+
+        let text = VERSION.format(OutputFormat::Text).unwrap();
+        assert!(text.starts_with(&format!("mz {} (", env!("CARGO_PKG_VERSION"))));
+        assert_eq!(text.lines().count(), 1);
+
+        let description = VERSION.describe(OutputFormat::Text).unwrap();
+        assert_eq!(description.lines().count(), 3);
+        assert!(description.contains(BUILD_INFO.sha));
+        assert_eq!(
+            VERSION.describe(OutputFormat::Json).unwrap(),
+            VERSION.format(OutputFormat::Json).unwrap()
+        );
+    }
+}
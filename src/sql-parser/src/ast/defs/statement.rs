@@ -1751,25 +1751,46 @@ impl<T: AstInfo> AstDisplay for ShowSchemasStatement<T> {
 }
 impl_display_t!(ShowSchemasStatement);
 
-/// `SHOW <object>S`
+/// `SHOW [FULL] <object>S`
 ///
 /// ```sql
 /// SHOW TABLES;
 /// SHOW SOURCES;
 /// SHOW VIEWS;
 /// SHOW SINKS;
+/// SHOW FULL CONNECTIONS;
+/// SHOW CLUSTER REPLICAS FROM default;
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ShowObjectsStatement<T: AstInfo> {
     pub object_type: ObjectType,
     pub from: Option<T::SchemaName>,
+    /// The cluster of the objects, given as `FROM <cluster>` for cluster
+    /// replicas and as `IN CLUSTER <cluster>` otherwise.
     pub in_cluster: Option<T::ClusterName>,
+    pub full: bool,
+    /// Whether to list the system tables too, as MySQL's `SHOW EXTENDED TABLES`.
+    pub extended: bool,
+    /// Whether to list the ids of the objects too.
+    pub verbose: bool,
     pub filter: Option<ShowStatementFilter<T>>,
+    /// Whether a full listing is ordered by `created_at`, and if so whether
+    /// in ascending order, as in `SHOW FULL TABLES ORDER BY created_at DESC`.
+    pub order_by_created_at: Option<bool>,
 }
 
 impl<T: AstInfo> AstDisplay for ShowObjectsStatement<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str("SHOW");
+        if self.verbose {
+            f.write_str(" VERBOSE");
+        }
+        if self.extended {
+            f.write_str(" EXTENDED");
+        }
+        if self.full {
+            f.write_str(" FULL");
+        }
         f.write_str(" ");
         f.write_str(match &self.object_type {
             ObjectType::Table => "TABLES",
@@ -1791,13 +1812,23 @@ impl<T: AstInfo> AstDisplay for ShowObjectsStatement<T> {
             f.write_node(from);
         }
         if let Some(cluster) = &self.in_cluster {
-            f.write_str(" IN CLUSTER ");
+            if self.object_type == ObjectType::ClusterReplica {
+                f.write_str(" FROM ");
+            } else {
+                f.write_str(" IN CLUSTER ");
+            }
             f.write_node(cluster);
         }
         if let Some(filter) = &self.filter {
             f.write_str(" ");
             f.write_node(filter);
         }
+        if let Some(asc) = self.order_by_created_at {
+            f.write_str(" ORDER BY created_at");
+            if !asc {
+                f.write_str(" DESC");
+            }
+        }
     }
 }
 impl_display_t!(ShowObjectsStatement);
@@ -1808,12 +1839,16 @@ pub struct ShowIndexesStatement<T: AstInfo> {
     pub on_object: Option<T::ObjectName>,
     pub from_schema: Option<T::SchemaName>,
     pub in_cluster: Option<T::ClusterName>,
+    pub extended: bool,
     pub filter: Option<ShowStatementFilter<T>>,
 }
 
 impl<T: AstInfo> AstDisplay for ShowIndexesStatement<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str("SHOW ");
+        if self.extended {
+            f.write_str("EXTENDED ");
+        }
         f.write_str("INDEXES");
         if let Some(on_object) = &self.on_object {
             f.write_str(" ON ");
@@ -1835,18 +1870,198 @@ impl<T: AstInfo> AstDisplay for ShowIndexesStatement<T> {
 }
 impl_display_t!(ShowIndexesStatement);
 
+/// `SHOW SUBSOURCES FROM <source>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShowSubsourcesStatement<T: AstInfo> {
+    pub from_source: T::ObjectName,
+    pub filter: Option<ShowStatementFilter<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for ShowSubsourcesStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SHOW SUBSOURCES FROM ");
+        f.write_node(&self.from_source);
+        if let Some(filter) = &self.filter {
+            f.write_str(" ");
+            f.write_node(filter);
+        }
+    }
+}
+impl_display_t!(ShowSubsourcesStatement);
+
+/// `SHOW SOURCE ERRORS FROM <source>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShowSourceErrorsStatement<T: AstInfo> {
+    pub source_name: T::ObjectName,
+}
+
+impl<T: AstInfo> AstDisplay for ShowSourceErrorsStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SHOW SOURCE ERRORS FROM ");
+        f.write_node(&self.source_name);
+    }
+}
+impl_display_t!(ShowSourceErrorsStatement);
+
+/// `SHOW OBJECTS USING CONNECTION <connection> [LIKE | WHERE]`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShowObjectsUsingConnectionStatement<T: AstInfo> {
+    pub connection_name: T::ObjectName,
+    pub filter: Option<ShowStatementFilter<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for ShowObjectsUsingConnectionStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SHOW OBJECTS USING CONNECTION ");
+        f.write_node(&self.connection_name);
+        if let Some(filter) = &self.filter {
+            f.write_str(" ");
+            f.write_node(filter);
+        }
+    }
+}
+impl_display_t!(ShowObjectsUsingConnectionStatement);
+
+/// `SHOW OBJECTS [FROM <schema>] [LIKE | WHERE] ORDER BY DEPENDENCY`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShowObjectsByDependencyStatement<T: AstInfo> {
+    pub from: Option<T::SchemaName>,
+    pub filter: Option<ShowStatementFilter<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for ShowObjectsByDependencyStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SHOW OBJECTS");
+        if let Some(from) = &self.from {
+            f.write_str(" FROM ");
+            f.write_node(from);
+        }
+        if let Some(filter) = &self.filter {
+            f.write_str(" ");
+            f.write_node(filter);
+        }
+        f.write_str(" ORDER BY DEPENDENCY");
+    }
+}
+impl_display_t!(ShowObjectsByDependencyStatement);
+
+/// `SHOW TEMPORARY OBJECTS [LIKE | WHERE]`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShowTemporaryObjectsStatement<T: AstInfo> {
+    pub filter: Option<ShowStatementFilter<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for ShowTemporaryObjectsStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SHOW TEMPORARY OBJECTS");
+        if let Some(filter) = &self.filter {
+            f.write_str(" ");
+            f.write_node(filter);
+        }
+    }
+}
+impl_display_t!(ShowTemporaryObjectsStatement);
+
+/// `SHOW SOURCE LAG [FROM <schema>] [LIKE | WHERE]`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShowSourceLagStatement<T: AstInfo> {
+    pub from: Option<T::SchemaName>,
+    pub filter: Option<ShowStatementFilter<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for ShowSourceLagStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SHOW SOURCE LAG");
+        if let Some(from) = &self.from {
+            f.write_str(" FROM ");
+            f.write_node(from);
+        }
+        if let Some(filter) = &self.filter {
+            f.write_str(" ");
+            f.write_node(filter);
+        }
+    }
+}
+impl_display_t!(ShowSourceLagStatement);
+
+/// `SHOW TIMESTAMP`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShowTimestampStatement {}
+
+impl AstDisplay for ShowTimestampStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SHOW TIMESTAMP");
+    }
+}
+impl_display!(ShowTimestampStatement);
+
+/// `SHOW CATALOG SUMMARY`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShowCatalogSummaryStatement {}
+
+impl AstDisplay for ShowCatalogSummaryStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SHOW CATALOG SUMMARY");
+    }
+}
+impl_display!(ShowCatalogSummaryStatement);
+
+/// `SHOW AUDIT EVENTS [LIKE | WHERE] [LIMIT <n>]`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShowAuditEventsStatement<T: AstInfo> {
+    pub filter: Option<ShowStatementFilter<T>>,
+    pub limit: Option<u64>,
+}
+
+impl<T: AstInfo> AstDisplay for ShowAuditEventsStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SHOW AUDIT EVENTS");
+        if let Some(filter) = &self.filter {
+            f.write_str(" ");
+            f.write_node(filter);
+        }
+        if let Some(limit) = &self.limit {
+            f.write_str(" LIMIT ");
+            f.write_str(limit);
+        }
+    }
+}
+impl_display_t!(ShowAuditEventsStatement);
+
+/// `SHOW ... FORMAT JSON`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShowFormatJsonStatement<T: AstInfo> {
+    pub show: Box<ShowStatement<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for ShowFormatJsonStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.show);
+        f.write_str(" FORMAT JSON");
+    }
+}
+impl_display_t!(ShowFormatJsonStatement);
+
 /// `SHOW COLUMNS`
 ///
 /// Note: this is a MySQL-specific statement.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ShowColumnsStatement<T: AstInfo> {
     pub table_name: T::ObjectName,
+    pub full: bool,
+    pub extended: bool,
     pub filter: Option<ShowStatementFilter<T>>,
 }
 
 impl<T: AstInfo> AstDisplay for ShowColumnsStatement<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str("SHOW ");
+        if self.extended {
+            f.write_str("EXTENDED ");
+        }
+        if self.full {
+            f.write_str("FULL ");
+        }
         f.write_str("COLUMNS FROM ");
         f.write_node(&self.table_name);
         if let Some(filter) = &self.filter {
@@ -1857,6 +2072,20 @@ impl<T: AstInfo> AstDisplay for ShowColumnsStatement<T> {
 }
 impl_display_t!(ShowColumnsStatement);
 
+/// `SHOW KEYS FROM <relation>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShowKeysStatement<T: AstInfo> {
+    pub relation_name: T::ObjectName,
+}
+
+impl<T: AstInfo> AstDisplay for ShowKeysStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SHOW KEYS FROM ");
+        f.write_node(&self.relation_name);
+    }
+}
+impl_display_t!(ShowKeysStatement);
+
 /// `SHOW CREATE VIEW <view>`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ShowCreateViewStatement<T: AstInfo> {
@@ -1941,6 +2170,20 @@ impl<T: AstInfo> AstDisplay for ShowCreateIndexStatement<T> {
 }
 impl_display_t!(ShowCreateIndexStatement);
 
+/// `SHOW CREATE SCHEMA <schema>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShowCreateSchemaStatement<T: AstInfo> {
+    pub schema_name: T::SchemaName,
+}
+
+impl<T: AstInfo> AstDisplay for ShowCreateSchemaStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SHOW CREATE SCHEMA ");
+        f.write_node(&self.schema_name);
+    }
+}
+impl_display_t!(ShowCreateSchemaStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ShowCreateConnectionStatement<T: AstInfo> {
     pub connection_name: T::ObjectName,
@@ -2709,7 +2952,18 @@ pub enum ShowStatement<T: AstInfo> {
     ShowSchemas(ShowSchemasStatement<T>),
     ShowObjects(ShowObjectsStatement<T>),
     ShowIndexes(ShowIndexesStatement<T>),
+    ShowSubsources(ShowSubsourcesStatement<T>),
+    ShowSourceErrors(ShowSourceErrorsStatement<T>),
+    ShowSourceLag(ShowSourceLagStatement<T>),
+    ShowObjectsUsingConnection(ShowObjectsUsingConnectionStatement<T>),
+    ShowObjectsByDependency(ShowObjectsByDependencyStatement<T>),
+    ShowTemporaryObjects(ShowTemporaryObjectsStatement<T>),
+    ShowTimestamp(ShowTimestampStatement),
+    ShowCatalogSummary(ShowCatalogSummaryStatement),
+    ShowAuditEvents(ShowAuditEventsStatement<T>),
+    ShowFormatJson(ShowFormatJsonStatement<T>),
     ShowColumns(ShowColumnsStatement<T>),
+    ShowKeys(ShowKeysStatement<T>),
     ShowCreateView(ShowCreateViewStatement<T>),
     ShowCreateMaterializedView(ShowCreateMaterializedViewStatement<T>),
     ShowCreateSource(ShowCreateSourceStatement<T>),
@@ -2717,6 +2971,7 @@ pub enum ShowStatement<T: AstInfo> {
     ShowCreateSink(ShowCreateSinkStatement<T>),
     ShowCreateIndex(ShowCreateIndexStatement<T>),
     ShowCreateConnection(ShowCreateConnectionStatement<T>),
+    ShowCreateSchema(ShowCreateSchemaStatement<T>),
     ShowVariable(ShowVariableStatement),
 }
 
@@ -2727,7 +2982,18 @@ impl<T: AstInfo> AstDisplay for ShowStatement<T> {
             ShowStatement::ShowSchemas(stmt) => f.write_node(stmt),
             ShowStatement::ShowObjects(stmt) => f.write_node(stmt),
             ShowStatement::ShowIndexes(stmt) => f.write_node(stmt),
+            ShowStatement::ShowSubsources(stmt) => f.write_node(stmt),
+            ShowStatement::ShowSourceErrors(stmt) => f.write_node(stmt),
+            ShowStatement::ShowSourceLag(stmt) => f.write_node(stmt),
+            ShowStatement::ShowObjectsUsingConnection(stmt) => f.write_node(stmt),
+            ShowStatement::ShowObjectsByDependency(stmt) => f.write_node(stmt),
+            ShowStatement::ShowTemporaryObjects(stmt) => f.write_node(stmt),
+            ShowStatement::ShowTimestamp(stmt) => f.write_node(stmt),
+            ShowStatement::ShowCatalogSummary(stmt) => f.write_node(stmt),
+            ShowStatement::ShowAuditEvents(stmt) => f.write_node(stmt),
+            ShowStatement::ShowFormatJson(stmt) => f.write_node(stmt),
             ShowStatement::ShowColumns(stmt) => f.write_node(stmt),
+            ShowStatement::ShowKeys(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateView(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateMaterializedView(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateSource(stmt) => f.write_node(stmt),
@@ -2735,6 +3001,7 @@ impl<T: AstInfo> AstDisplay for ShowStatement<T> {
             ShowStatement::ShowCreateSink(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateIndex(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateConnection(stmt) => f.write_node(stmt),
+            ShowStatement::ShowCreateSchema(stmt) => f.write_node(stmt),
             ShowStatement::ShowVariable(stmt) => f.write_node(stmt),
         }
     }
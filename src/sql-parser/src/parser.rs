@@ -261,7 +261,14 @@ impl<'a> Parser<'a> {
                 Token::Keyword(COPY) => Ok(self.parse_copy()?),
                 Token::Keyword(SET) => Ok(self.parse_set()?),
                 Token::Keyword(RESET) => Ok(self.parse_reset()?),
-                Token::Keyword(SHOW) => Ok(Statement::Show(self.parse_show()?)),
+                Token::Keyword(SHOW) => {
+                    let show = self.parse_show()?;
+                    if self.parse_keyword(INTO) {
+                        self.parse_show_into(show)
+                    } else {
+                        Ok(Statement::Show(show))
+                    }
+                }
                 Token::Keyword(START) => Ok(self.parse_start_transaction()?),
                 // `BEGIN` is a nonstandard but common alias for the
                 // standard `START TRANSACTION` statement. It is supported
@@ -4740,14 +4747,118 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_show(&mut self) -> Result<ShowStatement<Raw>, ParserError> {
+        let show = self.parse_show_listing()?;
+        if self.parse_keywords(&[FORMAT, JSON]) {
+            Ok(ShowStatement::ShowFormatJson(ShowFormatJsonStatement {
+                show: Box::new(show),
+            }))
+        } else {
+            Ok(show)
+        }
+    }
+
+    fn parse_show_listing(&mut self) -> Result<ShowStatement<Raw>, ParserError> {
         if self.parse_keyword(DATABASES) {
+            // Databases are not contained in anything, so they cannot be
+            // scoped like the other objects.
+            if self.parse_keyword(FROM) {
+                return parser_err!(
+                    self,
+                    self.peek_prev_pos(),
+                    "SHOW DATABASES does not support FROM; use LIKE or WHERE to filter databases"
+                );
+            }
             return Ok(ShowStatement::ShowDatabases(ShowDatabasesStatement {
                 filter: self.parse_show_statement_filter()?,
             }));
         }
 
+        if self.parse_keyword(VERBOSE) {
+            let pos = self.peek_prev_pos();
+            return match self.parse_show_listing()? {
+                ShowStatement::ShowObjects(stmt) if !stmt.verbose => {
+                    Ok(ShowStatement::ShowObjects(ShowObjectsStatement {
+                        verbose: true,
+                        ..stmt
+                    }))
+                }
+                _ => parser_err!(
+                    self,
+                    pos,
+                    "VERBOSE is only supported when listing objects, e.g. SHOW VERBOSE TABLES"
+                ),
+            };
+        }
+
+        if self.parse_keyword(EXTENDED) {
+            // As in MySQL, only the tables, the indexes, and the columns have
+            // an extended listing. Only that of the indexes has no full one.
+            let full = self.parse_keyword(FULL);
+            let objects: &[Keyword] = if full {
+                &[COLUMNS, FIELDS, TABLES]
+            } else {
+                &[COLUMNS, FIELDS, INDEXES, TABLES]
+            };
+            return match self.expect_one_of_keywords(objects)? {
+                COLUMNS | FIELDS => self.parse_show_columns(true, full),
+                INDEXES => self.parse_show_indexes(true),
+                _ => match self.parse_show_objects(ObjectType::Table, full)? {
+                    ShowStatement::ShowObjects(stmt) => {
+                        Ok(ShowStatement::ShowObjects(ShowObjectsStatement {
+                            extended: true,
+                            ..stmt
+                        }))
+                    }
+                    _ => unreachable!("tables are always listed as objects"),
+                },
+            };
+        }
+
+        if self.parse_keyword(FULL) {
+            // Not every object type has a full listing so far, point users of
+            // the other object types to their regular listing.
+            let pos = self.peek_pos();
+            let objects = if let Some(objects) =
+                self.parse_one_of_keywords(&[CLUSTERS, OBJECTS, ROLES, SECRETS, TYPES, USERS])
+            {
+                objects.as_str()
+            } else if self.parse_keywords(&[CLUSTER, REPLICAS]) {
+                "CLUSTER REPLICAS"
+            } else if self.parse_keywords(&[MATERIALIZED, VIEWS]) {
+                return self.parse_show_objects(ObjectType::MaterializedView, true);
+            } else if self.parse_one_of_keywords(&[COLUMNS, FIELDS]).is_some() {
+                return self.parse_show_columns(false, true);
+            } else {
+                let object_type = match self.expect_one_of_keywords(&[
+                    CONNECTIONS,
+                    SINKS,
+                    SOURCES,
+                    TABLES,
+                    VIEWS,
+                ])? {
+                    CONNECTIONS => ObjectType::Connection,
+                    SINKS => ObjectType::Sink,
+                    SOURCES => ObjectType::Source,
+                    TABLES => ObjectType::Table,
+                    VIEWS => ObjectType::View,
+                    _ => unreachable!(),
+                };
+                return self.parse_show_objects(object_type, true);
+            };
+            return parser_err!(
+                self,
+                pos,
+                format!("SHOW FULL {objects} is not yet supported; use SHOW {objects}")
+            );
+        }
+
         if self.parse_one_of_keywords(&[COLUMNS, FIELDS]).is_some() {
-            self.parse_show_columns()
+            self.parse_show_columns(false, false)
+        } else if self.parse_keyword(KEYS) {
+            self.expect_one_of_keywords(&[FROM, IN])?;
+            Ok(ShowStatement::ShowKeys(ShowKeysStatement {
+                relation_name: self.parse_raw_name()?,
+            }))
         } else if self.parse_keyword(SCHEMAS) {
             let from = if self.parse_keyword(FROM) {
                 Some(self.parse_database_name()?)
@@ -4758,6 +4869,19 @@ impl<'a> Parser<'a> {
                 from,
                 filter: self.parse_show_statement_filter()?,
             }))
+        } else if self.parse_keywords(&[TEMPORARY, OBJECTS]) {
+            Ok(ShowStatement::ShowTemporaryObjects(
+                ShowTemporaryObjectsStatement {
+                    filter: self.parse_show_statement_filter()?,
+                },
+            ))
+        } else if self.parse_keywords(&[OBJECTS, USING, CONNECTION]) {
+            Ok(ShowStatement::ShowObjectsUsingConnection(
+                ShowObjectsUsingConnectionStatement {
+                    connection_name: self.parse_raw_name()?,
+                    filter: self.parse_show_statement_filter()?,
+                },
+            ))
         } else if let Some(object_type) = self.parse_one_of_keywords(&[
             OBJECTS,
             ROLES,
@@ -4780,8 +4904,15 @@ impl<'a> Parser<'a> {
                     if self.parse_keyword(REPLICAS) {
                         ObjectType::ClusterReplica
                     } else {
+                        // The session's active cluster, and replica, if one is
+                        // targeted, are session variables.
+                        let variable = if self.parse_keyword(REPLICA) {
+                            "cluster_replica"
+                        } else {
+                            "cluster"
+                        };
                         return Ok(ShowStatement::ShowVariable(ShowVariableStatement {
-                            variable: Ident::from("cluster"),
+                            variable: Ident::from(variable),
                         }));
                     }
                 }
@@ -4799,52 +4930,47 @@ impl<'a> Parser<'a> {
                 CONNECTIONS => ObjectType::Connection,
                 _ => unreachable!(),
             };
-
-            let from = if self.parse_keywords(&[FROM]) {
-                Some(self.parse_schema_name()?)
-            } else {
-                None
-            };
-
-            // Only Materialized Views and Indexes (handled separately below) are associated with clusters.
-            let in_cluster = if matches!(object_type, ObjectType::MaterializedView) {
-                self.parse_optional_in_cluster()?
-            } else {
-                None
-            };
-
-            Ok(ShowStatement::ShowObjects(ShowObjectsStatement {
-                object_type,
-                from,
-                in_cluster,
+            self.parse_show_objects(object_type, false)
+        } else if self.parse_keyword(INDEXES) {
+            self.parse_show_indexes(false)
+        } else if self.parse_keyword(SUBSOURCES) {
+            self.expect_keyword(FROM)?;
+            Ok(ShowStatement::ShowSubsources(ShowSubsourcesStatement {
+                from_source: self.parse_raw_name()?,
                 filter: self.parse_show_statement_filter()?,
             }))
-        } else if self.parse_keyword(INDEXES) {
-            let from_schema = if self.parse_keywords(&[FROM]) {
-                Some(self.parse_schema_name()?)
+        } else if self.parse_keywords(&[AUDIT, EVENTS]) {
+            let filter = self.parse_show_statement_filter()?;
+            let limit = if self.parse_keyword(LIMIT) {
+                Some(self.parse_literal_uint()?)
             } else {
                 None
             };
-            let on_object = if self.parse_one_of_keywords(&[ON]).is_some() {
-                Some(self.parse_raw_name()?)
+            Ok(ShowStatement::ShowAuditEvents(ShowAuditEventsStatement {
+                filter,
+                limit,
+            }))
+        } else if self.parse_keywords(&[SOURCE, ERRORS]) {
+            self.expect_keyword(FROM)?;
+            Ok(ShowStatement::ShowSourceErrors(ShowSourceErrorsStatement {
+                source_name: self.parse_raw_name()?,
+            }))
+        } else if self.parse_keywords(&[SOURCE, LAG]) {
+            let from = if self.parse_keyword(FROM) {
+                Some(self.parse_schema_name()?)
             } else {
                 None
             };
-            if from_schema.is_some() && on_object.is_some() {
-                return parser_err!(
-                    self,
-                    self.peek_prev_pos(),
-                    "Cannot specify both FROM and ON"
-                );
-            }
-            let in_cluster = self.parse_optional_in_cluster()?;
-
-            Ok(ShowStatement::ShowIndexes(ShowIndexesStatement {
-                on_object,
-                from_schema,
-                in_cluster,
+            Ok(ShowStatement::ShowSourceLag(ShowSourceLagStatement {
+                from,
                 filter: self.parse_show_statement_filter()?,
             }))
+        } else if self.parse_keyword(TIMESTAMP) {
+            Ok(ShowStatement::ShowTimestamp(ShowTimestampStatement {}))
+        } else if self.parse_keywords(&[CATALOG, SUMMARY]) {
+            Ok(ShowStatement::ShowCatalogSummary(
+                ShowCatalogSummaryStatement {},
+            ))
         } else if self.parse_keywords(&[CREATE, VIEW]) {
             Ok(ShowStatement::ShowCreateView(ShowCreateViewStatement {
                 view_name: self.parse_raw_name()?,
@@ -4877,6 +5003,10 @@ impl<'a> Parser<'a> {
                     connection_name: self.parse_raw_name()?,
                 },
             ))
+        } else if self.parse_keywords(&[CREATE, SCHEMA]) {
+            Ok(ShowStatement::ShowCreateSchema(ShowCreateSchemaStatement {
+                schema_name: self.parse_schema_name()?,
+            }))
         } else {
             let variable = if self.parse_keywords(&[TRANSACTION, ISOLATION, LEVEL]) {
                 Ident::new("transaction_isolation")
@@ -4891,7 +5021,143 @@ impl<'a> Parser<'a> {
         }
     }
 
-    fn parse_show_columns(&mut self) -> Result<ShowStatement<Raw>, ParserError> {
+    fn parse_show_objects(
+        &mut self,
+        object_type: ObjectType,
+        full: bool,
+    ) -> Result<ShowStatement<Raw>, ParserError> {
+        // Cluster replicas are scoped by their cluster rather than by a schema.
+        if object_type == ObjectType::ClusterReplica {
+            let in_cluster = if self.parse_keyword(FROM) {
+                Some(self.parse_raw_ident()?)
+            } else {
+                None
+            };
+            return Ok(ShowStatement::ShowObjects(ShowObjectsStatement {
+                object_type,
+                from: None,
+                in_cluster,
+                full,
+                extended: false,
+                verbose: false,
+                filter: self.parse_show_statement_filter()?,
+                order_by_created_at: None,
+            }));
+        }
+
+        let from = if self.parse_keywords(&[FROM]) {
+            Some(self.parse_schema_name()?)
+        } else {
+            None
+        };
+
+        // Only Materialized Views and Indexes (handled separately below) are associated with clusters.
+        let in_cluster = if matches!(object_type, ObjectType::MaterializedView) {
+            self.parse_optional_in_cluster()?
+        } else {
+            None
+        };
+
+        let filter = self.parse_show_statement_filter()?;
+        if object_type == ObjectType::Object && self.parse_keywords(&[ORDER, BY, DEPENDENCY]) {
+            return Ok(ShowStatement::ShowObjectsByDependency(
+                ShowObjectsByDependencyStatement { from, filter },
+            ));
+        }
+
+        // The full listings of the objects that the audit log records can be
+        // ordered by when the objects were created.
+        let has_created_at = matches!(
+            object_type,
+            ObjectType::Table
+                | ObjectType::View
+                | ObjectType::MaterializedView
+                | ObjectType::Source
+                | ObjectType::Sink
+        );
+        let order_by_created_at = if full && has_created_at && self.parse_keywords(&[ORDER, BY]) {
+            if self.parse_identifier()?.as_str() != "created_at" {
+                return parser_err!(
+                    self,
+                    self.peek_prev_pos(),
+                    "full listings can only be ordered by created_at"
+                );
+            }
+            Some(self.parse_one_of_keywords(&[ASC, DESC]) != Some(DESC))
+        } else {
+            None
+        };
+
+        Ok(ShowStatement::ShowObjects(ShowObjectsStatement {
+            object_type,
+            from,
+            in_cluster,
+            full,
+            extended: false,
+            verbose: false,
+            filter,
+            order_by_created_at,
+        }))
+    }
+
+    /// Parses the `TEMP VIEW <name>` that a `SHOW ... INTO` materializes its
+    /// result as. It is equivalent to `CREATE TEMPORARY VIEW <name> AS SHOW ...`,
+    /// so that the result can be queried further in the session.
+    fn parse_show_into(&mut self, show: ShowStatement<Raw>) -> Result<Statement<Raw>, ParserError> {
+        self.expect_one_of_keywords(&[TEMP, TEMPORARY])?;
+        self.expect_keyword(VIEW)?;
+        let name = self.parse_object_name()?;
+        Ok(Statement::CreateView(CreateViewStatement {
+            if_exists: IfExistsBehavior::Error,
+            temporary: true,
+            definition: ViewDefinition {
+                name,
+                columns: vec![],
+                query: Query {
+                    ctes: vec![],
+                    body: SetExpr::Show(show),
+                    order_by: vec![],
+                    limit: None,
+                    offset: None,
+                },
+            },
+        }))
+    }
+
+    fn parse_show_indexes(&mut self, extended: bool) -> Result<ShowStatement<Raw>, ParserError> {
+        let from_schema = if self.parse_keywords(&[FROM]) {
+            Some(self.parse_schema_name()?)
+        } else {
+            None
+        };
+        let on_object = if self.parse_one_of_keywords(&[ON]).is_some() {
+            Some(self.parse_raw_name()?)
+        } else {
+            None
+        };
+        if from_schema.is_some() && on_object.is_some() {
+            return parser_err!(
+                self,
+                self.peek_prev_pos(),
+                "Cannot specify both FROM and ON"
+            );
+        }
+        let in_cluster = self.parse_optional_in_cluster()?;
+
+        Ok(ShowStatement::ShowIndexes(ShowIndexesStatement {
+            on_object,
+            from_schema,
+            in_cluster,
+            extended,
+            filter: self.parse_show_statement_filter()?,
+        }))
+    }
+
+    fn parse_show_columns(
+        &mut self,
+        extended: bool,
+        full: bool,
+    ) -> Result<ShowStatement<Raw>, ParserError> {
         self.expect_one_of_keywords(&[FROM, IN])?;
         let table_name = self.parse_raw_name()?;
         // MySQL also supports FROM <database> here. In other words, MySQL
@@ -4900,6 +5166,8 @@ impl<'a> Parser<'a> {
         let filter = self.parse_show_statement_filter()?;
         Ok(ShowStatement::ShowColumns(ShowColumnsStatement {
             table_name,
+            full,
+            extended,
             filter,
         }))
     }
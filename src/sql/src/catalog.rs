@@ -259,6 +259,9 @@ pub trait CatalogSchema {
 
     /// Lists the `CatalogItem`s for the schema.
     fn has_items(&self) -> bool;
+
+    /// Returns the IDs of the items in the schema.
+    fn item_ids(&self) -> Box<dyn Iterator<Item = GlobalId> + '_>;
 }
 
 /// A role in a [`SessionCatalog`].
@@ -36,6 +36,12 @@ pub enum PlanError {
         feature: String,
         issue_no: Option<usize>,
     },
+    /// A `SHOW` statement not yet supported, along with the closest
+    /// supported alternative.
+    UnsupportedShow {
+        statement: String,
+        alternative: String,
+    },
     UnknownColumn {
         table: Option<PartialObjectName>,
         column: ColumnName,
@@ -158,6 +164,13 @@ impl fmt::Display for PlanError {
                 }
                 Ok(())
             }
+            Self::UnsupportedShow {
+                statement,
+                alternative,
+            } => write!(
+                f,
+                "{statement} is not yet supported; use {alternative}"
+            ),
             Self::UnknownColumn { table, column } => write!(
                 f,
                 "column {} does not exist",
@@ -1295,6 +1295,7 @@ fn plan_set_expr(
                 ShowStatement::ShowColumns(stmt) => {
                     show::show_columns(qcx.scx, stmt)?.plan_hir(qcx)
                 }
+                ShowStatement::ShowKeys(stmt) => show::show_keys(qcx.scx, stmt)?.plan_hir(qcx),
                 ShowStatement::ShowCreateConnection(stmt) => to_hirscope(
                     show::plan_show_create_connection(qcx.scx, stmt.clone())?,
                     show::describe_show_create_connection(qcx.scx, stmt)?,
@@ -1323,6 +1324,10 @@ fn plan_set_expr(
                     show::plan_show_create_materialized_view(qcx.scx, stmt.clone())?,
                     show::describe_show_create_materialized_view(qcx.scx, stmt)?,
                 ),
+                ShowStatement::ShowCreateSchema(stmt) => to_hirscope(
+                    show::plan_show_create_schema(qcx.scx, stmt.clone())?,
+                    show::describe_show_create_schema(qcx.scx, stmt)?,
+                ),
                 ShowStatement::ShowDatabases(stmt) => {
                     show::show_databases(qcx.scx, stmt)?.plan_hir(qcx)
                 }
@@ -1335,6 +1340,36 @@ fn plan_set_expr(
                 ShowStatement::ShowSchemas(stmt) => {
                     show::show_schemas(qcx.scx, stmt)?.plan_hir(qcx)
                 }
+                ShowStatement::ShowSubsources(stmt) => {
+                    show::show_subsources(qcx.scx, stmt)?.plan_hir(qcx)
+                }
+                ShowStatement::ShowSourceErrors(stmt) => {
+                    show::show_source_errors(qcx.scx, stmt)?.plan_hir(qcx)
+                }
+                ShowStatement::ShowSourceLag(stmt) => {
+                    show::show_source_lag(qcx.scx, stmt)?.plan_hir(qcx)
+                }
+                ShowStatement::ShowObjectsUsingConnection(stmt) => {
+                    show::show_objects_using_connection(qcx.scx, stmt)?.plan_hir(qcx)
+                }
+                ShowStatement::ShowObjectsByDependency(stmt) => {
+                    show::show_objects_by_dependency(qcx.scx, stmt)?.plan_hir(qcx)
+                }
+                ShowStatement::ShowTemporaryObjects(stmt) => {
+                    show::show_temporary_objects(qcx.scx, stmt)?.plan_hir(qcx)
+                }
+                ShowStatement::ShowTimestamp(stmt) => {
+                    show::show_timestamp(qcx.scx, stmt)?.plan_hir(qcx)
+                }
+                ShowStatement::ShowCatalogSummary(stmt) => {
+                    show::show_catalog_summary(qcx.scx, stmt)?.plan_hir(qcx)
+                }
+                ShowStatement::ShowAuditEvents(stmt) => {
+                    show::show_audit_events(qcx.scx, stmt)?.plan_hir(qcx)
+                }
+                ShowStatement::ShowFormatJson(stmt) => {
+                    show::show_format_json(qcx.scx, stmt)?.plan_hir(qcx)
+                }
                 ShowStatement::ShowVariable(_) => sql_bail!("unsupported SHOW statement"),
             }
         }
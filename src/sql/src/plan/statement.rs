@@ -145,6 +145,9 @@ pub fn describe(
         Statement::Show(ShowStatement::ShowColumns(stmt)) => {
             show::show_columns(&scx, stmt)?.describe()?
         }
+        Statement::Show(ShowStatement::ShowKeys(stmt)) => {
+            show::show_keys(&scx, stmt)?.describe()?
+        }
         Statement::Show(ShowStatement::ShowCreateConnection(stmt)) => {
             show::describe_show_create_connection(&scx, stmt)?
         }
@@ -166,6 +169,9 @@ pub fn describe(
         Statement::Show(ShowStatement::ShowCreateMaterializedView(stmt)) => {
             show::describe_show_create_materialized_view(&scx, stmt)?
         }
+        Statement::Show(ShowStatement::ShowCreateSchema(stmt)) => {
+            show::describe_show_create_schema(&scx, stmt)?
+        }
         Statement::Show(ShowStatement::ShowDatabases(stmt)) => {
             show::show_databases(&scx, stmt)?.describe()?
         }
@@ -178,6 +184,36 @@ pub fn describe(
         Statement::Show(ShowStatement::ShowSchemas(stmt)) => {
             show::show_schemas(&scx, stmt)?.describe()?
         }
+        Statement::Show(ShowStatement::ShowSubsources(stmt)) => {
+            show::show_subsources(&scx, stmt)?.describe()?
+        }
+        Statement::Show(ShowStatement::ShowSourceErrors(stmt)) => {
+            show::show_source_errors(&scx, stmt)?.describe()?
+        }
+        Statement::Show(ShowStatement::ShowSourceLag(stmt)) => {
+            show::show_source_lag(&scx, stmt)?.describe()?
+        }
+        Statement::Show(ShowStatement::ShowObjectsUsingConnection(stmt)) => {
+            show::show_objects_using_connection(&scx, stmt)?.describe()?
+        }
+        Statement::Show(ShowStatement::ShowObjectsByDependency(stmt)) => {
+            show::show_objects_by_dependency(&scx, stmt)?.describe()?
+        }
+        Statement::Show(ShowStatement::ShowTemporaryObjects(stmt)) => {
+            show::show_temporary_objects(&scx, stmt)?.describe()?
+        }
+        Statement::Show(ShowStatement::ShowTimestamp(stmt)) => {
+            show::show_timestamp(&scx, stmt)?.describe()?
+        }
+        Statement::Show(ShowStatement::ShowCatalogSummary(stmt)) => {
+            show::show_catalog_summary(&scx, stmt)?.describe()?
+        }
+        Statement::Show(ShowStatement::ShowAuditEvents(stmt)) => {
+            show::show_audit_events(&scx, stmt)?.describe()?
+        }
+        Statement::Show(ShowStatement::ShowFormatJson(stmt)) => {
+            show::show_format_json(&scx, stmt)?.describe()?
+        }
 
         // SCL statements.
         Statement::Close(stmt) => scl::describe_close(&scx, stmt)?,
@@ -293,6 +329,7 @@ pub fn plan(
 
         // `SHOW` statements.
         Statement::Show(ShowStatement::ShowColumns(stmt)) => show::show_columns(scx, stmt)?.plan(),
+        Statement::Show(ShowStatement::ShowKeys(stmt)) => show::show_keys(scx, stmt)?.plan(),
         Statement::Show(ShowStatement::ShowCreateConnection(stmt)) => {
             show::plan_show_create_connection(scx, stmt).map(Plan::SendRows)
         }
@@ -314,12 +351,45 @@ pub fn plan(
         Statement::Show(ShowStatement::ShowCreateMaterializedView(stmt)) => {
             show::plan_show_create_materialized_view(scx, stmt).map(Plan::SendRows)
         }
+        Statement::Show(ShowStatement::ShowCreateSchema(stmt)) => {
+            show::plan_show_create_schema(scx, stmt).map(Plan::SendRows)
+        }
         Statement::Show(ShowStatement::ShowDatabases(stmt)) => {
             show::show_databases(scx, stmt)?.plan()
         }
         Statement::Show(ShowStatement::ShowIndexes(stmt)) => show::show_indexes(scx, stmt)?.plan(),
         Statement::Show(ShowStatement::ShowObjects(stmt)) => show::show_objects(scx, stmt)?.plan(),
         Statement::Show(ShowStatement::ShowSchemas(stmt)) => show::show_schemas(scx, stmt)?.plan(),
+        Statement::Show(ShowStatement::ShowSubsources(stmt)) => {
+            show::show_subsources(scx, stmt)?.plan()
+        }
+        Statement::Show(ShowStatement::ShowSourceErrors(stmt)) => {
+            show::show_source_errors(scx, stmt)?.plan()
+        }
+        Statement::Show(ShowStatement::ShowSourceLag(stmt)) => {
+            show::show_source_lag(scx, stmt)?.plan()
+        }
+        Statement::Show(ShowStatement::ShowObjectsUsingConnection(stmt)) => {
+            show::show_objects_using_connection(scx, stmt)?.plan()
+        }
+        Statement::Show(ShowStatement::ShowObjectsByDependency(stmt)) => {
+            show::show_objects_by_dependency(scx, stmt)?.plan()
+        }
+        Statement::Show(ShowStatement::ShowTemporaryObjects(stmt)) => {
+            show::show_temporary_objects(scx, stmt)?.plan()
+        }
+        Statement::Show(ShowStatement::ShowTimestamp(stmt)) => {
+            show::show_timestamp(scx, stmt)?.plan()
+        }
+        Statement::Show(ShowStatement::ShowCatalogSummary(stmt)) => {
+            show::show_catalog_summary(scx, stmt)?.plan()
+        }
+        Statement::Show(ShowStatement::ShowAuditEvents(stmt)) => {
+            show::show_audit_events(scx, stmt)?.plan()
+        }
+        Statement::Show(ShowStatement::ShowFormatJson(stmt)) => {
+            show::show_format_json(scx, stmt)?.plan()
+        }
 
         // SCL statements.
         Statement::Close(stmt) => scl::plan_close(scx, stmt),
@@ -522,7 +592,12 @@ impl<'a> StatementContext<'a> {
     }
 
     pub fn resolve_active_schema(&self) -> Result<&SchemaSpecifier, PlanError> {
-        Ok(self.catalog.resolve_schema(None, DEFAULT_SCHEMA)?.id())
+        Ok(self.active_schema()?.id())
+    }
+
+    /// Returns the schema that applies when a statement does not name one.
+    pub fn active_schema(&self) -> Result<&dyn CatalogSchema, PlanError> {
+        Ok(self.catalog.resolve_schema(None, DEFAULT_SCHEMA)?)
     }
 
     pub fn resolve_database(
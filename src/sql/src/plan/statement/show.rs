@@ -13,24 +13,33 @@
 //! `SHOW CREATE TABLE` and `SHOW VIEWS`. Note that `SHOW <var>` is considered
 //! an SCL statement.
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Write;
 
 use mz_ore::collections::CollectionExt;
-use mz_repr::{Datum, RelationDesc, Row, ScalarType};
+use mz_repr::{Datum, GlobalId, RelationDesc, Row, ScalarType};
 use mz_sql_parser::ast::display::AstDisplay;
-use mz_sql_parser::ast::{ShowCreateConnectionStatement, ShowCreateMaterializedViewStatement};
+use mz_sql_parser::ast::{
+    ShowCreateConnectionStatement, ShowCreateMaterializedViewStatement, ShowCreateSchemaStatement,
+};
+use mz_storage_client::types::connections::Connection;
 use query::QueryContext;
 
 use crate::ast::visit_mut::VisitMut;
 use crate::ast::{
-    ObjectType, SelectStatement, ShowColumnsStatement, ShowCreateIndexStatement,
+    Expr, Limit, ObjectType, Raw, SelectItem, SelectStatement, SetExpr, ShowAuditEventsStatement,
+    ShowCatalogSummaryStatement, ShowColumnsStatement, ShowCreateIndexStatement,
     ShowCreateSinkStatement, ShowCreateSourceStatement, ShowCreateTableStatement,
-    ShowCreateViewStatement, ShowDatabasesStatement, ShowIndexesStatement, ShowObjectsStatement,
-    ShowSchemasStatement, ShowStatementFilter, Statement, Value,
+    ShowCreateViewStatement, ShowDatabasesStatement, ShowFormatJsonStatement, ShowIndexesStatement,
+    ShowKeysStatement, ShowObjectsByDependencyStatement, ShowObjectsStatement,
+    ShowObjectsUsingConnectionStatement, ShowSchemasStatement, ShowSourceErrorsStatement,
+    ShowSourceLagStatement, ShowStatement, ShowStatementFilter, ShowSubsourcesStatement,
+    ShowTemporaryObjectsStatement, ShowTimestampStatement, Statement, Value,
 };
-use crate::catalog::{CatalogItemType, SessionCatalog};
+use crate::catalog::{CatalogItemType, CatalogSchema, SessionCatalog};
 use crate::names::{
     self, Aug, NameSimplifier, ResolvedClusterName, ResolvedDatabaseName, ResolvedSchemaName,
+    SchemaSpecifier,
 };
 use crate::parse;
 use crate::plan::scope::Scope;
@@ -264,6 +273,78 @@ pub fn plan_show_create_connection(
     }
 }
 
+pub fn describe_show_create_schema(
+    _: &StatementContext,
+    _: ShowCreateSchemaStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(Some(
+        RelationDesc::empty()
+            .with_column("name", ScalarType::String.nullable(false))
+            .with_column("create_sql", ScalarType::String.nullable(false)),
+    )))
+}
+
+pub fn plan_show_create_schema(
+    scx: &StatementContext,
+    ShowCreateSchemaStatement { schema_name }: ShowCreateSchemaStatement<Aug>,
+) -> Result<SendRowsPlan, PlanError> {
+    let (database_spec, schema_spec, full_name) = match &schema_name {
+        ResolvedSchemaName::Schema {
+            database_spec,
+            schema_spec,
+            full_name,
+        } => (database_spec, schema_spec, full_name),
+        ResolvedSchemaName::Error => {
+            unreachable!("should have been handled by name resolution")
+        }
+    };
+    if scx.catalog.is_system_schema(&full_name.schema) {
+        sql_bail!("cannot show create for system schema {}", full_name);
+    }
+    let schema = scx.get_schema(database_spec, schema_spec);
+
+    // Emit the items in dependency order, so that the statements can be
+    // replayed one after the other. Visiting the items in ID order keeps
+    // the output deterministic.
+    fn visit(
+        scx: &StatementContext,
+        id: GlobalId,
+        item_ids: &BTreeSet<GlobalId>,
+        visited: &mut BTreeSet<GlobalId>,
+        sorted: &mut Vec<GlobalId>,
+    ) {
+        if !visited.insert(id) {
+            return;
+        }
+        for dep in scx.get_item(&id).uses() {
+            if item_ids.contains(dep) {
+                visit(scx, *dep, item_ids, visited, sorted);
+            }
+        }
+        sorted.push(id);
+    }
+
+    let item_ids: BTreeSet<_> = schema.item_ids().collect();
+    let mut visited = BTreeSet::new();
+    let mut sorted = Vec::with_capacity(item_ids.len());
+    for id in &item_ids {
+        visit(scx, *id, &item_ids, &mut visited, &mut sorted);
+    }
+
+    let mut rows = Vec::with_capacity(sorted.len());
+    for id in sorted {
+        let item = scx.get_item(&id);
+        let name = scx.catalog.resolve_full_name(item.name()).to_string();
+        let create_sql = simplify_names(scx.catalog, item.create_sql())?;
+        rows.push(Row::pack_slice(&[
+            Datum::String(&name),
+            Datum::String(&create_sql),
+        ]));
+    }
+
+    Ok(SendRowsPlan { rows })
+}
+
 pub fn show_databases<'a>(
     scx: &'a StatementContext<'a>,
     ShowDatabasesStatement { filter }: ShowDatabasesStatement<Aug>,
@@ -300,87 +381,324 @@ pub fn show_objects<'a>(
         object_type,
         from,
         in_cluster,
+        full,
+        extended,
+        verbose,
         filter,
+        order_by_created_at,
     }: ShowObjectsStatement<Aug>,
 ) -> Result<ShowSelect<'a>, PlanError> {
+    let has_full_listing = matches!(
+        object_type,
+        ObjectType::Table
+            | ObjectType::Source
+            | ObjectType::View
+            | ObjectType::MaterializedView
+            | ObjectType::Sink
+            | ObjectType::Connection
+    );
+    if full && !has_full_listing {
+        return Err(PlanError::UnsupportedShow {
+            statement: format!("SHOW FULL {}S", object_type),
+            alternative: format!("SHOW {}S", object_type),
+        });
+    }
+
+    if extended && object_type != ObjectType::Table {
+        return Err(PlanError::UnsupportedShow {
+            statement: format!("SHOW EXTENDED {}S", object_type),
+            alternative: format!("SHOW {}S", object_type),
+        });
+    }
+
+    let order = created_at_order(order_by_created_at);
     match object_type {
-        ObjectType::Table => show_tables(scx, from, filter),
-        ObjectType::Source => show_sources(scx, from, filter),
-        ObjectType::View => show_views(scx, from, filter),
-        ObjectType::MaterializedView => show_materialized_views(scx, from, in_cluster, filter),
-        ObjectType::Sink => show_sinks(scx, from, filter),
-        ObjectType::Type => show_types(scx, from, filter),
-        ObjectType::Object => show_all_objects(scx, from, filter),
-        ObjectType::Role => bail_unsupported!("SHOW ROLES"),
-        ObjectType::Cluster => show_clusters(scx, filter),
-        ObjectType::ClusterReplica => show_cluster_replicas(scx, filter),
-        ObjectType::Secret => show_secrets(scx, from, filter),
+        ObjectType::Table => show_tables(scx, from, extended, full, verbose, filter, order),
+        ObjectType::Source => show_sources(scx, from, full, verbose, filter, order),
+        ObjectType::View => show_views(scx, from, full, verbose, filter, order),
+        ObjectType::MaterializedView => {
+            show_materialized_views(scx, from, in_cluster, full, verbose, filter, order)
+        }
+        ObjectType::Sink => show_sinks(scx, from, full, verbose, filter, order),
+        ObjectType::Type => show_types(scx, from, verbose, filter),
+        ObjectType::Object => show_all_objects(scx, from, verbose, filter),
+        ObjectType::Role => Err(PlanError::UnsupportedShow {
+            statement: "SHOW ROLES".into(),
+            alternative: "SELECT name FROM mz_catalog.mz_roles".into(),
+        }),
+        ObjectType::Cluster => show_clusters(scx, verbose, filter),
+        ObjectType::ClusterReplica => show_cluster_replicas(scx, in_cluster, verbose, filter),
+        ObjectType::Secret => show_secrets(scx, from, verbose, filter),
         ObjectType::Index => unreachable!("SHOW INDEX handled separately"),
-        ObjectType::Connection => show_connections(scx, from, filter),
+        ObjectType::Connection => show_connections(scx, from, full, verbose, filter),
+    }
+}
+
+/// Returns the schema whose objects a `SHOW` lists: the schema it names, or
+/// else the active schema, as [`StatementContext::resolve_optional_schema`]
+/// does for the listing itself.
+fn listed_schema<'a>(
+    scx: &'a StatementContext,
+    from: &Option<ResolvedSchemaName>,
+) -> Result<&'a dyn CatalogSchema, PlanError> {
+    match from {
+        Some(ResolvedSchemaName::Schema {
+            database_spec,
+            schema_spec,
+            ..
+        }) => Ok(scx.get_schema(database_spec, schema_spec)),
+        None => scx.active_schema(),
+        Some(ResolvedSchemaName::Error) => {
+            unreachable!("should have been handled by name resolution")
+        }
     }
 }
 
 fn show_connections<'a>(
     scx: &'a StatementContext<'a>,
     from: Option<ResolvedSchemaName>,
+    full: bool,
+    verbose: bool,
     filter: Option<ShowStatementFilter<Aug>>,
 ) -> Result<ShowSelect<'a>, PlanError> {
+    let created_at = created_at_column(verbose, "'connection'", "mz_connections.id");
+    let qualified_name = verbose_qualified_name(verbose, "mz_connections");
+    let id = verbose_id(verbose);
     let schema_spec = scx.resolve_optional_schema(&from)?;
-    let query = format!(
-        "SELECT name, type
-        FROM mz_catalog.mz_connections
-        WHERE schema_id = {schema_spec}",
-    );
+    let query = if full {
+        let schema = listed_schema(scx, &from)?;
+        // The details only include the public endpoints of the connection, as
+        // they are known to the catalog. Secrets are never rendered.
+        let mut details = vec![];
+        for id in schema.item_ids() {
+            let item = scx.get_item(&id);
+            if item.item_type() != CatalogItemType::Connection {
+                continue;
+            }
+            if let Some(endpoint) = connection_endpoint(item.connection()?) {
+                details.push(format!("('{}', {})", id, Value::String(endpoint)));
+            }
+        }
+        if details.is_empty() {
+            details.push("(NULL, NULL)".into());
+        }
+        format!(
+            "SELECT name, type, details{created_at}{qualified_name}{id}
+            FROM mz_catalog.mz_connections
+            LEFT JOIN (VALUES {}) AS d (id, details) USING (id)
+            WHERE schema_id = {schema_spec}",
+            itertools::join(details, ", "),
+        )
+    } else {
+        format!(
+            "SELECT name, type{created_at}{qualified_name}{id}
+            FROM mz_catalog.mz_connections
+            WHERE schema_id = {schema_spec}",
+        )
+    };
     ShowSelect::new(scx, query, filter, None, None)
 }
 
+/// Returns the column listing the ids of the objects, last, in verbose listings.
+fn verbose_id(verbose: bool) -> &'static str {
+    if verbose {
+        ", id"
+    } else {
+        ""
+    }
+}
+
+/// Returns the `qualified_name` column of verbose listings: the
+/// `database.schema.name` reference of the object in `relation`, which is
+/// unambiguous across databases and schemas. The objects of ambient schemas,
+/// like `mz_catalog`, are qualified by their schema only.
+fn verbose_qualified_name(verbose: bool, relation: &str) -> String {
+    if verbose {
+        format!(
+            ", (SELECT concat(databases.name || '.', schemas.name, '.', {relation}.name)
+            FROM mz_catalog.mz_schemas AS schemas
+            LEFT JOIN mz_catalog.mz_databases AS databases ON databases.id = schemas.database_id
+            WHERE schemas.id = {relation}.schema_id) AS qualified_name"
+        )
+    } else {
+        String::new()
+    }
+}
+
+/// Renders the public endpoint of a connection, if it has one.
+fn connection_endpoint(connection: &Connection) -> Option<String> {
+    match connection {
+        Connection::Kafka(kafka) => Some(
+            kafka
+                .brokers
+                .iter()
+                .map(|broker| broker.address.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        Connection::Csr(csr) => Some(csr.url.to_string()),
+        Connection::Postgres(postgres) => Some(format!(
+            "{}:{}/{}",
+            postgres.host, postgres.port, postgres.database
+        )),
+        Connection::Ssh(ssh) => Some(format!("{}:{}", ssh.host, ssh.port)),
+        Connection::Aws(aws) => aws.endpoint.as_ref().map(|endpoint| endpoint.to_string()),
+        Connection::AwsPrivatelink(privatelink) => Some(privatelink.service_name.clone()),
+    }
+}
+
+/// Returns the `created_at` column of the full and verbose listings: when the
+/// object was created, according to the audit log. The `object_type` is an
+/// expression, so that listings of several types of objects can read it from
+/// a column. It is `NULL` for the objects that the audit log does not record,
+/// such as the system ones.
+fn created_at_column(listed: bool, object_type: &str, id: &str) -> String {
+    if listed {
+        format!(
+            ", (SELECT min(occurred_at)
+            FROM mz_catalog.mz_audit_events
+            WHERE event_type = 'create'
+                AND object_type = {object_type}
+                AND details->>'id' = {id}) AS created_at"
+        )
+    } else {
+        String::new()
+    }
+}
+
+/// Returns the order of a full listing ordered by its `created_at` column, in
+/// ascending order for `Some(true)`. The objects without a creation time are
+/// listed last, and ties are listed by name.
+fn created_at_order(order_by_created_at: Option<bool>) -> Option<&'static str> {
+    order_by_created_at.map(|asc| {
+        if asc {
+            "created_at NULLS LAST, name"
+        } else {
+            "created_at DESC NULLS LAST, name"
+        }
+    })
+}
+
+/// Returns the `status` column of the source and sink listings: the latest
+/// status of the object in its status `history`, e.g. `running` or `stalled`.
+/// It is `NULL` for the objects without a status history, such as the system
+/// ones.
+fn latest_status(history: &str, id_column: &str, id: &str) -> String {
+    format!(
+        ", (SELECT status
+        FROM mz_internal.{history}
+        WHERE {id_column} = {id}
+        ORDER BY occurred_at DESC
+        LIMIT 1) AS status"
+    )
+}
+
+/// Returns the projection of the source and sink listings. The `status` column
+/// is always computed, so that the listings can be filtered by it, but only the
+/// full listings show it.
+fn status_projection(full: bool, verbose: bool) -> Option<Vec<&'static str>> {
+    if full {
+        return None;
+    }
+    let mut projection = vec!["name", "type", "size"];
+    if verbose {
+        projection.extend(["created_at", "qualified_name", "id"]);
+    }
+    Some(projection)
+}
+
+/// Returns the predicate selecting the objects of the schema. The extended
+/// listing, as in MySQL, also selects the objects of the system schemas.
+fn schema_predicate(schema_spec: &SchemaSpecifier, extended: bool) -> String {
+    if extended {
+        format!(
+            "(schema_id = {schema_spec} \
+            OR schema_id IN (SELECT id FROM mz_catalog.mz_schemas WHERE database_id IS NULL))"
+        )
+    } else {
+        format!("schema_id = {schema_spec}")
+    }
+}
+
+/// Lists the tables of a schema. The extended listing also lists the tables of
+/// the system schemas, those outside of any database, as `SHOW SCHEMAS` does.
 fn show_tables<'a>(
     scx: &'a StatementContext<'a>,
     from: Option<ResolvedSchemaName>,
+    extended: bool,
+    full: bool,
+    verbose: bool,
     filter: Option<ShowStatementFilter<Aug>>,
+    order: Option<&str>,
 ) -> Result<ShowSelect<'a>, PlanError> {
+    let created_at = created_at_column(full || verbose, "'table'", "mz_tables.id");
+    let qualified_name = verbose_qualified_name(verbose, "mz_tables");
+    let id = verbose_id(verbose);
     let schema_spec = scx.resolve_optional_schema(&from)?;
+    let where_clause = schema_predicate(&schema_spec, extended);
     let query = format!(
-        "SELECT name
+        "SELECT name{created_at}{qualified_name}{id}
         FROM mz_catalog.mz_tables
-        WHERE schema_id = {schema_spec}",
+        WHERE {where_clause}",
     );
-    ShowSelect::new(scx, query, filter, None, None)
+    ShowSelect::new(scx, query, filter, order, None)
+}
+
+/// Returns the query listing the sources of the schema.
+fn sources_query(schema_spec: &SchemaSpecifier, full: bool, verbose: bool) -> String {
+    let status = latest_status("mz_source_status_history", "source_id", "mz_sources.id");
+    let created_at = created_at_column(full || verbose, "'source'", "mz_sources.id");
+    let qualified_name = verbose_qualified_name(verbose, "mz_sources");
+    let id = verbose_id(verbose);
+    format!(
+        "SELECT name, type, size{status}{created_at}{qualified_name}{id}
+        FROM mz_catalog.mz_sources
+        WHERE schema_id = {schema_spec}"
+    )
 }
 
 fn show_sources<'a>(
     scx: &'a StatementContext<'a>,
     from: Option<ResolvedSchemaName>,
+    full: bool,
+    verbose: bool,
     filter: Option<ShowStatementFilter<Aug>>,
+    order: Option<&str>,
 ) -> Result<ShowSelect<'a>, PlanError> {
     let schema_spec = scx.resolve_optional_schema(&from)?;
-    let query = format!(
-        "SELECT name, type, size
-        FROM mz_catalog.mz_sources
-        WHERE schema_id = {schema_spec}"
-    );
-    ShowSelect::new(scx, query, filter, None, None)
+    let query = sources_query(&schema_spec, full, verbose);
+    let projection = status_projection(full, verbose);
+    ShowSelect::new(scx, query, filter, order, projection.as_deref())
 }
 
 fn show_views<'a>(
     scx: &'a StatementContext<'a>,
     from: Option<ResolvedSchemaName>,
+    full: bool,
+    verbose: bool,
     filter: Option<ShowStatementFilter<Aug>>,
+    order: Option<&str>,
 ) -> Result<ShowSelect<'a>, PlanError> {
+    let created_at = created_at_column(full || verbose, "'view'", "mz_views.id");
+    let qualified_name = verbose_qualified_name(verbose, "mz_views");
+    let id = verbose_id(verbose);
     let schema_spec = scx.resolve_optional_schema(&from)?;
     let query = format!(
-        "SELECT name
+        "SELECT name{created_at}{qualified_name}{id}
         FROM mz_catalog.mz_views
         WHERE schema_id = {schema_spec}"
     );
-    ShowSelect::new(scx, query, filter, None, None)
+    ShowSelect::new(scx, query, filter, order, None)
 }
 
 fn show_materialized_views<'a>(
     scx: &'a StatementContext<'a>,
     from: Option<ResolvedSchemaName>,
     in_cluster: Option<ResolvedClusterName>,
+    full: bool,
+    verbose: bool,
     filter: Option<ShowStatementFilter<Aug>>,
+    order: Option<&str>,
 ) -> Result<ShowSelect<'a>, PlanError> {
     let schema_spec = scx.resolve_optional_schema(&from)?;
     let mut where_clause = format!("schema_id = {schema_spec}");
@@ -390,41 +708,67 @@ fn show_materialized_views<'a>(
             .expect("write on string cannot fail");
     }
 
+    // The materialized views are identified by their schema and name.
+    let join = if verbose || full {
+        " JOIN mz_catalog.mz_materialized_views USING (name, schema_id, cluster_id)"
+    } else {
+        ""
+    };
+    let created_at = created_at_column(
+        full || verbose,
+        "'materialized-view'",
+        "mz_materialized_views.id",
+    );
+    let qualified_name = verbose_qualified_name(verbose, "mz_show_materialized_views");
+    let id = verbose_id(verbose);
     let query = format!(
-        "SELECT name, cluster
-         FROM mz_internal.mz_show_materialized_views
+        "SELECT name, cluster{created_at}{qualified_name}{id}
+         FROM mz_internal.mz_show_materialized_views{join}
          WHERE {where_clause}"
     );
 
-    ShowSelect::new(scx, query, filter, None, None)
+    ShowSelect::new(scx, query, filter, order, None)
+}
+
+/// Returns the query listing the sinks of the schema.
+fn sinks_query(schema_spec: &SchemaSpecifier, full: bool, verbose: bool) -> String {
+    let status = latest_status("mz_sink_status_history", "sink_id", "sinks.id");
+    let created_at = created_at_column(full || verbose, "'sink'", "sinks.id");
+    let qualified_name = verbose_qualified_name(verbose, "sinks");
+    let id = verbose_id(verbose);
+    format!(
+        "SELECT sinks.name, sinks.type, sinks.size{status}{created_at}{qualified_name}{id}
+         FROM mz_catalog.mz_sinks AS sinks
+         WHERE schema_id = {schema_spec}",
+    )
 }
 
 fn show_sinks<'a>(
     scx: &'a StatementContext<'a>,
     from: Option<ResolvedSchemaName>,
+    full: bool,
+    verbose: bool,
     filter: Option<ShowStatementFilter<Aug>>,
+    order: Option<&str>,
 ) -> Result<ShowSelect<'a>, PlanError> {
-    let schema_spec = if let Some(ResolvedSchemaName::Schema { schema_spec, .. }) = from {
-        schema_spec.to_string()
-    } else {
-        scx.resolve_active_schema()?.to_string()
-    };
-    let query = format!(
-        "SELECT sinks.name, sinks.type, sinks.size
-         FROM mz_catalog.mz_sinks AS sinks
-         WHERE schema_id = {schema_spec}",
-    );
-    ShowSelect::new(scx, query, filter, None, None)
+    let schema_spec = scx.resolve_optional_schema(&from)?;
+    let query = sinks_query(&schema_spec, full, verbose);
+    let projection = status_projection(full, verbose);
+    ShowSelect::new(scx, query, filter, order, projection.as_deref())
 }
 
 fn show_types<'a>(
     scx: &'a StatementContext<'a>,
     from: Option<ResolvedSchemaName>,
+    verbose: bool,
     filter: Option<ShowStatementFilter<Aug>>,
 ) -> Result<ShowSelect<'a>, PlanError> {
+    let created_at = created_at_column(verbose, "'type'", "mz_types.id");
+    let qualified_name = verbose_qualified_name(verbose, "mz_types");
+    let id = verbose_id(verbose);
     let schema_spec = scx.resolve_optional_schema(&from)?;
     let query = format!(
-        "SELECT name
+        "SELECT name{created_at}{qualified_name}{id}
         FROM mz_catalog.mz_types
         WHERE schema_id = {schema_spec}",
     );
@@ -434,11 +778,15 @@ fn show_types<'a>(
 fn show_all_objects<'a>(
     scx: &'a StatementContext<'a>,
     from: Option<ResolvedSchemaName>,
+    verbose: bool,
     filter: Option<ShowStatementFilter<Aug>>,
 ) -> Result<ShowSelect<'a>, PlanError> {
+    let created_at = created_at_column(verbose, "mz_objects.type", "mz_objects.id");
+    let qualified_name = verbose_qualified_name(verbose, "mz_objects");
+    let id = verbose_id(verbose);
     let schema_spec = scx.resolve_optional_schema(&from)?;
     let query = format!(
-        "SELECT name, type
+        "SELECT name, type{created_at}{qualified_name}{id}
         FROM mz_catalog.mz_objects
         WHERE schema_id = {schema_spec}",
     );
@@ -451,15 +799,19 @@ pub fn show_indexes<'a>(
         in_cluster,
         on_object,
         from_schema,
+        extended,
         filter,
     }: ShowIndexesStatement<Aug>,
 ) -> Result<ShowSelect<'a>, PlanError> {
     let mut query_filter = Vec::new();
 
     if on_object.is_none() && from_schema.is_none() && in_cluster.is_none() {
-        query_filter.push("on_id NOT LIKE 's%'".into());
+        // The extended listing also lists the indexes on the system objects.
+        if !extended {
+            query_filter.push("on_id NOT LIKE 's%'".into());
+        }
         let schema_spec = scx.resolve_active_schema().map(|spec| spec.clone())?;
-        query_filter.push(format!("schema_id = {}", schema_spec));
+        query_filter.push(schema_predicate(&schema_spec, extended));
     }
 
     if let Some(on_object) = on_object {
@@ -479,8 +831,7 @@ pub fn show_indexes<'a>(
     }
 
     if let Some(schema) = from_schema {
-        let schema_spec = schema.schema_spec();
-        query_filter.push(format!("schema_id = {}", schema_spec));
+        query_filter.push(schema_predicate(schema.schema_spec(), extended));
     }
 
     if let Some(cluster) = in_cluster {
@@ -497,9 +848,353 @@ pub fn show_indexes<'a>(
     ShowSelect::new(scx, query, filter, None, None)
 }
 
+pub fn show_subsources<'a>(
+    scx: &'a StatementContext<'a>,
+    ShowSubsourcesStatement {
+        from_source,
+        filter,
+    }: ShowSubsourcesStatement<Aug>,
+) -> Result<ShowSelect<'a>, PlanError> {
+    let source = scx.get_item_by_resolved_name(&from_source)?;
+    if source.item_type() != CatalogItemType::Source {
+        sql_bail!("{} is not a source", from_source.full_name_str());
+    }
+
+    let subsources = source.subsources();
+    let query_filter = if subsources.is_empty() {
+        "false".to_string()
+    } else {
+        format!(
+            "id IN ({})",
+            itertools::join(subsources.iter().map(|id| format!("'{}'", id)), ", ")
+        )
+    };
+
+    let query = format!(
+        "SELECT name, type
+        FROM mz_catalog.mz_sources
+        WHERE {query_filter}",
+    );
+    ShowSelect::new(scx, query, filter, None, None)
+}
+
+pub fn show_source_errors<'a>(
+    scx: &'a StatementContext<'a>,
+    ShowSourceErrorsStatement { source_name }: ShowSourceErrorsStatement<Aug>,
+) -> Result<ShowSelect<'a>, PlanError> {
+    let source = scx.get_item_by_resolved_name(&source_name)?;
+    if source.item_type() != CatalogItemType::Source {
+        sql_bail!("{} is not a source", source_name.full_name_str());
+    }
+
+    let errors = format!(
+        "SELECT occurred_at, error
+        FROM mz_internal.mz_source_status_history
+        WHERE source_id = '{}' AND error IS NOT NULL",
+        source.id(),
+    );
+    let query = format!(
+        "SELECT error, occurred_at
+        FROM ({errors}) errors
+        WHERE occurred_at = (SELECT max(occurred_at) FROM ({errors}) latest)",
+    );
+    ShowSelect::new(scx, query, None, None, None)
+}
+
+/// Lists the objects of a schema in an order in which they can be created,
+/// every object after the objects of the schema it depends on.
+pub fn show_objects_by_dependency<'a>(
+    scx: &'a StatementContext<'a>,
+    ShowObjectsByDependencyStatement { from, filter }: ShowObjectsByDependencyStatement<Aug>,
+) -> Result<ShowSelect<'a>, PlanError> {
+    let schema = listed_schema(scx, &from)?;
+
+    let objects = schema
+        .item_ids()
+        .map(|id| {
+            let item = scx.get_item(&id);
+            (id, (item.name().item.clone(), item.uses().to_vec()))
+        })
+        .collect();
+    let order = match dependency_order(&objects) {
+        Ok(order) => order,
+        Err(cycle) => sql_bail!(
+            "cannot order the objects by dependency, as {} depend on each other",
+            itertools::join(cycle, ", ")
+        ),
+    };
+
+    let mut positions = order
+        .iter()
+        .enumerate()
+        .map(|(position, id)| format!("('{}', {})", id, position))
+        .collect::<Vec<_>>();
+    if positions.is_empty() {
+        positions.push("(NULL, NULL)".into());
+    }
+    let query = format!(
+        "SELECT name, type, position
+        FROM mz_catalog.mz_objects
+        JOIN (VALUES {}) AS o (id, position) USING (id)",
+        itertools::join(positions, ", "),
+    );
+    ShowSelect::new(
+        scx,
+        query,
+        filter,
+        Some("position"),
+        Some(&["name", "type"]),
+    )
+}
+
+pub fn show_temporary_objects<'a>(
+    scx: &'a StatementContext<'a>,
+    ShowTemporaryObjectsStatement { filter }: ShowTemporaryObjectsStatement<Aug>,
+) -> Result<ShowSelect<'a>, PlanError> {
+    // Every session's temporary objects share the same schema ID in
+    // `mz_objects`, so the objects are scoped to the session's temporary schema
+    // by their IDs instead.
+    let ids = scx
+        .catalog
+        .resolve_schema(None, "mz_temp")?
+        .item_ids()
+        .map(|id| format!("'{}'", id))
+        .collect::<Vec<_>>();
+    let predicate = if ids.is_empty() {
+        "false".to_string()
+    } else {
+        format!("id IN ({})", itertools::join(ids, ", "))
+    };
+    let query = format!(
+        "SELECT name, type
+        FROM mz_catalog.mz_objects
+        WHERE {predicate}"
+    );
+    ShowSelect::new(scx, query, filter, None, None)
+}
+
+/// Orders objects so that every object comes after its dependencies, breaking
+/// ties by name. `objects` maps the id of every object to its name and the ids
+/// of the objects it depends on, which are ignored unless they are in `objects`.
+///
+/// Returns the names of the objects that are part of, or depend on, a cycle.
+fn dependency_order(
+    objects: &BTreeMap<GlobalId, (String, Vec<GlobalId>)>,
+) -> Result<Vec<GlobalId>, Vec<String>> {
+    let mut dependencies = BTreeMap::new();
+    let mut dependents = BTreeMap::<GlobalId, Vec<GlobalId>>::new();
+    for (id, (_, uses)) in objects {
+        let uses = uses
+            .iter()
+            .filter(|used| *used != id && objects.contains_key(used))
+            .collect::<BTreeSet<_>>();
+        for used in &uses {
+            dependents.entry(**used).or_default().push(*id);
+        }
+        dependencies.insert(*id, uses.len());
+    }
+
+    let mut ready = dependencies
+        .iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(id, _)| (&objects[id].0, *id))
+        .collect::<BTreeSet<_>>();
+    let mut order = vec![];
+    while let Some((name, id)) = ready.iter().next().cloned() {
+        ready.remove(&(name, id));
+        order.push(id);
+        for dependent in dependents.get(&id).into_iter().flatten() {
+            let count = dependencies
+                .get_mut(dependent)
+                .expect("dependents are objects");
+            *count -= 1;
+            if *count == 0 {
+                ready.insert((&objects[dependent].0, *dependent));
+            }
+        }
+    }
+
+    if order.len() < objects.len() {
+        let mut cycle = dependencies
+            .iter()
+            .filter(|(_, count)| **count > 0)
+            .map(|(id, _)| objects[id].0.clone())
+            .collect::<Vec<_>>();
+        cycle.sort();
+        return Err(cycle);
+    }
+    Ok(order)
+}
+
+/// Lists the sources and sinks that use a connection, e.g. to
+/// know which ones are affected by rotating or dropping it.
+pub fn show_objects_using_connection<'a>(
+    scx: &'a StatementContext<'a>,
+    ShowObjectsUsingConnectionStatement {
+        connection_name,
+        filter,
+    }: ShowObjectsUsingConnectionStatement<Aug>,
+) -> Result<ShowSelect<'a>, PlanError> {
+    let connection = scx.get_item_by_resolved_name(&connection_name)?;
+    if connection.item_type() != CatalogItemType::Connection {
+        sql_bail!("{} is not a connection", connection_name.full_name_str());
+    }
+
+    let query = format!(
+        "SELECT objects.name, objects.type
+        FROM (
+            SELECT name, 'source' AS type, connection_id FROM mz_catalog.mz_sources
+            UNION ALL
+            SELECT name, 'sink' AS type, connection_id FROM mz_catalog.mz_sinks
+        ) objects
+        WHERE connection_id = '{}'",
+        connection.id(),
+    );
+    ShowSelect::new(scx, query, filter, None, None)
+}
+
+/// Lists the sources of a schema, the furthest behind first.
+///
+/// The lag of a source is how far its frontier, as imported by the
+/// dataflows of the active cluster, trails the current time. Sources
+/// that no dataflow imports have no known lag, and are listed last.
+pub fn show_source_lag<'a>(
+    scx: &'a StatementContext<'a>,
+    ShowSourceLagStatement { from, filter }: ShowSourceLagStatement<Aug>,
+) -> Result<ShowSelect<'a>, PlanError> {
+    let schema_spec = scx.resolve_optional_schema(&from)?;
+    let query = format!(
+        "SELECT s.name, s.type, st.status, now() - to_timestamp(f.time::text::float8 / 1000) AS lag
+        FROM mz_catalog.mz_sources s
+        LEFT JOIN (
+            SELECT import_id, max(time) AS time
+            FROM mz_internal.mz_compute_import_frontiers
+            GROUP BY import_id
+        ) f ON s.id = f.import_id
+        LEFT JOIN (
+            SELECT DISTINCT ON (source_id) source_id, status
+            FROM mz_internal.mz_source_status_history
+            ORDER BY source_id, occurred_at DESC
+        ) st ON s.id = st.source_id
+        WHERE s.schema_id = {schema_spec}"
+    );
+    ShowSelect::new(scx, query, filter, Some("lag DESC NULLS LAST, name"), None)
+}
+
+/// Shows the timestamp at which the query reads, which within a
+/// transaction is the read timestamp of the transaction.
+pub fn show_timestamp<'a>(
+    scx: &'a StatementContext<'a>,
+    ShowTimestampStatement {}: ShowTimestampStatement,
+) -> Result<ShowSelect<'a>, PlanError> {
+    let query = "SELECT mz_now() AS timestamp".to_string();
+    ShowSelect::new(scx, query, None, None, None)
+}
+
+/// Shows the number of objects of each kind in the catalog, including the
+/// system ones, as a sense of the scale of the deployment.
+pub fn show_catalog_summary<'a>(
+    scx: &'a StatementContext<'a>,
+    ShowCatalogSummaryStatement {}: ShowCatalogSummaryStatement,
+) -> Result<ShowSelect<'a>, PlanError> {
+    let query = "SELECT
+            (SELECT count(*) FROM mz_catalog.mz_databases) AS databases,
+            (SELECT count(*) FROM mz_catalog.mz_schemas) AS schemas,
+            (SELECT count(*) FROM mz_catalog.mz_tables) AS tables,
+            (SELECT count(*) FROM mz_catalog.mz_views) AS views,
+            (SELECT count(*) FROM mz_catalog.mz_materialized_views) AS materialized_views,
+            (SELECT count(*) FROM mz_catalog.mz_sources) AS sources,
+            (SELECT count(*) FROM mz_catalog.mz_sinks) AS sinks,
+            (SELECT count(*) FROM mz_catalog.mz_indexes) AS indexes"
+        .to_string();
+    ShowSelect::new(scx, query, None, None, None)
+}
+
+pub fn show_audit_events<'a>(
+    scx: &'a StatementContext<'a>,
+    ShowAuditEventsStatement { filter, limit }: ShowAuditEventsStatement<Aug>,
+) -> Result<ShowSelect<'a>, PlanError> {
+    // The name of the object is recorded differently depending on its type.
+    let query = "SELECT
+            id,
+            occurred_at,
+            event_type,
+            object_type,
+            coalesce(
+                details->>'item',
+                details->'new_name'->>'item',
+                details->>'replica_name',
+                details->>'name'
+            ) AS name,
+            \"user\"
+        FROM mz_catalog.mz_audit_events"
+        .to_string();
+    let show_select = ShowSelect::new(
+        scx,
+        query,
+        filter,
+        Some("id DESC"),
+        Some(&[
+            "occurred_at",
+            "event_type",
+            "object_type",
+            "name",
+            "\"user\"",
+        ]),
+    )?;
+    Ok(show_select.with_limit(limit))
+}
+
+/// Returns the query listing the columns of the relation, along with the
+/// column it is ordered by and the columns it is projected onto.
+///
+/// The full listing adds the position of each column, as `field_number`, and
+/// the category of its type, e.g. `numeric` or `string`, as `category`.
+fn show_columns_query(id: GlobalId, full: bool) -> (String, &'static str, &'static [&'static str]) {
+    if full {
+        let query = format!(
+            "SELECT
+                mz_columns.name,
+                mz_columns.nullable,
+                mz_columns.type,
+                mz_columns.position AS field_number,
+                mz_types.category
+             FROM mz_catalog.mz_columns
+             LEFT JOIN mz_catalog.mz_types ON mz_columns.type_oid = mz_types.oid
+             WHERE mz_columns.id = '{}'",
+            id,
+        );
+        (
+            query,
+            "field_number",
+            &["name", "nullable", "type", "field_number", "category"],
+        )
+    } else {
+        let query = format!(
+            "SELECT
+                mz_columns.name,
+                mz_columns.nullable,
+                mz_columns.type,
+                mz_columns.position
+             FROM mz_catalog.mz_columns
+             WHERE mz_columns.id = '{}'",
+            id,
+        );
+        (query, "position", &["name", "nullable", "type"])
+    }
+}
+
 pub fn show_columns<'a>(
     scx: &'a StatementContext<'a>,
-    ShowColumnsStatement { table_name, filter }: ShowColumnsStatement<Aug>,
+    ShowColumnsStatement {
+        table_name,
+        full,
+        // In MySQL, the extended listing also shows the hidden columns of
+        // the table. Relations have no hidden columns, so it shows the same
+        // columns as the regular listing.
+        extended: _,
+        filter,
+    }: ShowColumnsStatement<Aug>,
 ) -> Result<ShowSelect<'a>, PlanError> {
     let entry = scx.get_item_by_resolved_name(&table_name)?;
     let full_name = scx.catalog.resolve_full_name(entry.name());
@@ -519,40 +1214,142 @@ pub fn show_columns<'a>(
         }
     }
 
-    let query = format!(
-        "SELECT
-            mz_columns.name,
-            mz_columns.nullable,
-            mz_columns.type,
-            mz_columns.position
-         FROM mz_catalog.mz_columns
-         WHERE mz_columns.id = '{}'",
-        entry.id(),
-    );
-    ShowSelect::new(
-        scx,
-        query,
-        filter,
-        Some("position"),
-        Some(&["name", "nullable", "type"]),
-    )
+    let (query, order, projection) = show_columns_query(entry.id(), full);
+    ShowSelect::new(scx, query, filter, Some(order), Some(projection))
+}
+
+pub fn show_keys<'a>(
+    scx: &'a StatementContext<'a>,
+    ShowKeysStatement { relation_name }: ShowKeysStatement<Aug>,
+) -> Result<ShowSelect<'a>, PlanError> {
+    let entry = scx.get_item_by_resolved_name(&relation_name)?;
+    let full_name = scx.catalog.resolve_full_name(entry.name());
+
+    match entry.item_type() {
+        CatalogItemType::Source
+        | CatalogItemType::Table
+        | CatalogItemType::View
+        | CatalogItemType::MaterializedView => (),
+        ty @ CatalogItemType::Connection
+        | ty @ CatalogItemType::Index
+        | ty @ CatalogItemType::Func
+        | ty @ CatalogItemType::Secret
+        | ty @ CatalogItemType::Type
+        | ty @ CatalogItemType::Sink => {
+            sql_bail!("{full_name} is a {ty} and so does not have keys");
+        }
+    }
+
+    // The keys are not in the system catalog, so they are listed from the
+    // relation's description instead, in the order they were declared or
+    // inferred in.
+    let desc = entry.desc(&full_name)?;
+    let keys = desc
+        .typ()
+        .keys
+        .iter()
+        .enumerate()
+        .map(|(position, key)| {
+            let columns = key.iter().map(|column| {
+                Value::String(desc.get_name(*column).as_str().into()).to_ast_string()
+            });
+            format!(
+                "({}, ARRAY[{}]::text[])",
+                position,
+                itertools::join(columns, ", ")
+            )
+        })
+        .collect::<Vec<_>>();
+    let query = if keys.is_empty() {
+        "SELECT * FROM (VALUES (0, ARRAY[]::text[])) AS k (position, key) WHERE false".to_string()
+    } else {
+        format!(
+            "SELECT * FROM (VALUES {}) AS k (position, key)",
+            itertools::join(keys, ", ")
+        )
+    };
+    ShowSelect::new(scx, query, None, Some("position"), Some(&["key"]))
+}
+
+pub fn show_format_json<'a>(
+    scx: &'a StatementContext<'a>,
+    ShowFormatJsonStatement { show }: ShowFormatJsonStatement<Aug>,
+) -> Result<ShowSelect<'a>, PlanError> {
+    let show_select = match *show {
+        ShowStatement::ShowColumns(stmt) => show_columns(scx, stmt)?,
+        ShowStatement::ShowKeys(stmt) => show_keys(scx, stmt)?,
+        ShowStatement::ShowDatabases(stmt) => show_databases(scx, stmt)?,
+        ShowStatement::ShowIndexes(stmt) => show_indexes(scx, stmt)?,
+        ShowStatement::ShowObjects(stmt) => show_objects(scx, stmt)?,
+        ShowStatement::ShowSchemas(stmt) => show_schemas(scx, stmt)?,
+        ShowStatement::ShowSubsources(stmt) => show_subsources(scx, stmt)?,
+        ShowStatement::ShowSourceErrors(stmt) => show_source_errors(scx, stmt)?,
+        ShowStatement::ShowSourceLag(stmt) => show_source_lag(scx, stmt)?,
+        ShowStatement::ShowObjectsUsingConnection(stmt) => {
+            show_objects_using_connection(scx, stmt)?
+        }
+        ShowStatement::ShowObjectsByDependency(stmt) => show_objects_by_dependency(scx, stmt)?,
+        ShowStatement::ShowTemporaryObjects(stmt) => show_temporary_objects(scx, stmt)?,
+        ShowStatement::ShowTimestamp(stmt) => show_timestamp(scx, stmt)?,
+        ShowStatement::ShowCatalogSummary(stmt) => show_catalog_summary(scx, stmt)?,
+        ShowStatement::ShowAuditEvents(stmt) => show_audit_events(scx, stmt)?,
+        ShowStatement::ShowFormatJson(_) => sql_bail!("FORMAT JSON cannot be repeated"),
+        ShowStatement::ShowCreateView(_)
+        | ShowStatement::ShowCreateMaterializedView(_)
+        | ShowStatement::ShowCreateSource(_)
+        | ShowStatement::ShowCreateTable(_)
+        | ShowStatement::ShowCreateSink(_)
+        | ShowStatement::ShowCreateIndex(_)
+        | ShowStatement::ShowCreateConnection(_)
+        | ShowStatement::ShowCreateSchema(_)
+        | ShowStatement::ShowVariable(_) => {
+            sql_bail!("FORMAT JSON is not supported for SHOW CREATE or SHOW <variable>")
+        }
+    };
+    show_select.with_json_format()
 }
 
 pub fn show_clusters<'a>(
     scx: &'a StatementContext<'a>,
+    verbose: bool,
     filter: Option<ShowStatementFilter<Aug>>,
 ) -> Result<ShowSelect<'a>, PlanError> {
-    let query = "SELECT mz_clusters.name FROM mz_catalog.mz_clusters".to_string();
+    let created_at = created_at_column(verbose, "'cluster'", "mz_clusters.id");
+    let id = verbose_id(verbose);
+    let query = format!("SELECT mz_clusters.name{created_at}{id} FROM mz_catalog.mz_clusters");
 
     ShowSelect::new(scx, query, filter, None, None)
 }
 
 pub fn show_cluster_replicas<'a>(
     scx: &'a StatementContext<'a>,
+    from_cluster: Option<ResolvedClusterName>,
+    verbose: bool,
     filter: Option<ShowStatementFilter<Aug>>,
 ) -> Result<ShowSelect<'a>, PlanError> {
-    let query = "SELECT cluster, replica, size, ready FROM mz_internal.mz_show_cluster_replicas"
-        .to_string();
+    let mut query = if verbose {
+        // The replicas are identified by the names of their cluster and their own.
+        "SELECT cluster, replica, size, ready, id
+        FROM mz_internal.mz_show_cluster_replicas
+        JOIN (
+            SELECT clusters.name AS cluster, replicas.name AS replica, replicas.id
+            FROM mz_catalog.mz_cluster_replicas AS replicas
+            JOIN mz_catalog.mz_clusters AS clusters ON replicas.cluster_id = clusters.id
+        ) AS ids USING (cluster, replica)"
+            .to_string()
+    } else {
+        "SELECT cluster, replica, size, ready FROM mz_internal.mz_show_cluster_replicas".to_string()
+    };
+
+    if let Some(cluster) = from_cluster {
+        let cluster = scx.catalog.get_compute_instance(cluster.id);
+        write!(
+            query,
+            " WHERE cluster = {}",
+            Value::String(cluster.name().to_string())
+        )
+        .expect("write on string cannot fail");
+    }
 
     ShowSelect::new(scx, query, filter, None, None)
 }
@@ -560,12 +1357,16 @@ pub fn show_cluster_replicas<'a>(
 pub fn show_secrets<'a>(
     scx: &'a StatementContext<'a>,
     from: Option<ResolvedSchemaName>,
+    verbose: bool,
     filter: Option<ShowStatementFilter<Aug>>,
 ) -> Result<ShowSelect<'a>, PlanError> {
+    let created_at = created_at_column(verbose, "'secret'", "mz_secrets.id");
+    let qualified_name = verbose_qualified_name(verbose, "mz_secrets");
+    let id = verbose_id(verbose);
     let schema_spec = scx.resolve_optional_schema(&from)?;
 
     let query = format!(
-        "SELECT name
+        "SELECT name{created_at}{qualified_name}{id}
         FROM mz_catalog.mz_secrets
         WHERE schema_id = {schema_spec}",
     );
@@ -589,7 +1390,8 @@ impl<'a> ShowSelect<'a> {
     /// Note that the query must return a column named `name`, as the filter
     /// may implicitly reference this column. Any `ORDER BY` in the query is
     /// ignored. `ShowSelects`s are always ordered in ascending order by all
-    /// columns from left to right unless an order field is supplied.
+    /// columns from left to right unless an order field is supplied, so
+    /// listings of the objects in a schema are ordered by their unique names.
     fn new(
         scx: &'a StatementContext,
         query: String,
@@ -602,6 +1404,9 @@ impl<'a> ShowSelect<'a> {
             Some(ShowStatementFilter::Where(expr)) => expr.to_string(),
             None => "true".to_string(),
         };
+        // The filter applies to the result of the query, rather than within
+        // it, so that it can reference any of its columns, including computed
+        // ones.
         let query = format!(
             "SELECT {} FROM ({}) q WHERE {} ORDER BY {}",
             projection
@@ -611,15 +1416,65 @@ impl<'a> ShowSelect<'a> {
             filter,
             order.unwrap_or("q.*")
         );
-        let stmts = parse::parse(&query).expect("ShowSelect::new called with invalid SQL");
-        let stmt = match stmts.into_element() {
-            Statement::Select(select) => select,
-            _ => panic!("ShowSelect::new called with non-SELECT statement"),
-        };
+        let stmt = parse_select(&query)?;
         let (stmt, _) = names::resolve(scx.catalog, stmt)?;
         Ok(ShowSelect { scx, stmt })
     }
 
+    /// Limits this `ShowSelect` to its first rows, if a limit is supplied.
+    fn with_limit(mut self, limit: Option<u64>) -> ShowSelect<'a> {
+        self.stmt.query.limit = limit.map(|limit| Limit {
+            with_ties: false,
+            quantity: Expr::Value(Value::Number(limit.to_string())),
+        });
+        self
+    }
+
+    /// Wraps the projection of this `ShowSelect` in a single `json` column, so
+    /// that each row is encoded as one JSON object keyed by the names of the
+    /// columns it replaces. The order of the rows is unchanged.
+    fn with_json_format(mut self) -> Result<ShowSelect<'a>, PlanError> {
+        let select = match &mut self.stmt.query.body {
+            SetExpr::Select(select) => select,
+            _ => sql_bail!("[internal error] ShowSelect with non-SELECT body"),
+        };
+        let object = match select.projection.as_slice() {
+            [SelectItem::Wildcard] => "to_jsonb(q)".to_string(),
+            items => {
+                let fields = items
+                    .iter()
+                    .map(|item| match item {
+                        SelectItem::Expr {
+                            expr: Expr::Identifier(names),
+                            alias: None,
+                        } => {
+                            let name = names.last().expect("identifiers are not empty");
+                            Ok(format!(
+                                "{}, {}",
+                                Value::String(name.as_str().to_string()),
+                                name.to_ast_string()
+                            ))
+                        }
+                        _ => sql_bail!(
+                            "[internal error] ShowSelect projection is not a column name: {}",
+                            item.to_ast_string()
+                        ),
+                    })
+                    .collect::<Result<Vec<_>, PlanError>>()?;
+                format!("jsonb_build_object({})", fields.join(", "))
+            }
+        };
+
+        let query = format!("SELECT {} AS json", object);
+        let stmt = parse_select(&query)?;
+        let (stmt, _) = names::resolve(self.scx.catalog, stmt)?;
+        match stmt.query.body {
+            SetExpr::Select(json) => select.projection = json.projection,
+            _ => sql_bail!("[internal error] SHOW generated a non-SELECT query: {query}"),
+        }
+        Ok(self)
+    }
+
     /// Computes the shape of this `ShowSelect`.
     pub fn describe(self) -> Result<StatementDesc, PlanError> {
         dml::describe_select(self.scx, self.stmt)
@@ -636,6 +1491,17 @@ impl<'a> ShowSelect<'a> {
     }
 }
 
+/// Parses a query generated by a `SHOW` statement, which must be a single
+/// `SELECT` statement. The query interpolates names and filters supplied by
+/// the user, so an invalid query is an error rather than a panic.
+fn parse_select(query: &str) -> Result<SelectStatement<Raw>, PlanError> {
+    let mut stmts = parse::parse(query)?;
+    match (stmts.pop(), stmts.is_empty()) {
+        (Some(Statement::Select(select)), true) => Ok(select),
+        _ => sql_bail!("[internal error] SHOW generated a non-SELECT query: {query}"),
+    }
+}
+
 fn simplify_names(catalog: &dyn SessionCatalog, sql: &str) -> Result<String, PlanError> {
     let parsed = parse::parse(sql)?.into_element();
     let (mut resolved, _) = names::resolve(catalog, parsed)?;
@@ -643,3 +1509,223 @@ fn simplify_names(catalog: &dyn SessionCatalog, sql: &str) -> Result<String, Pla
     simplifier.visit_statement_mut(&mut resolved);
     Ok(resolved.to_ast_string_stable())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use mz_repr::GlobalId;
+
+    use super::{
+        created_at_column, dependency_order, latest_status, schema_predicate, show_columns_query,
+        sinks_query, sources_query, ShowSelect,
+    };
+    use crate::ast::{Expr, Query, Select, SelectItem, SelectStatement, SetExpr, Value, Values};
+    use crate::catalog::DummyCatalog;
+    use crate::names::{SchemaId, SchemaSpecifier};
+    use crate::parse;
+    use crate::plan::statement::StatementContext;
+
+    #[test]
+    fn test_dependency_order() {
+        let objects = |graph: &[(u64, &str, &[u64])]| {
+            graph
+                .iter()
+                .map(|(id, name, uses)| {
+                    let uses = uses.iter().map(|id| GlobalId::User(*id)).collect();
+                    (GlobalId::User(*id), (name.to_string(), uses))
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        // A view over a join of two tables, indexed, and a table that nothing
+        // uses. Dependencies outside of the objects, like `s1`, are ignored.
+        let order = dependency_order(&objects(&[
+            (1, "v", &[4, 3]),
+            (2, "v_idx", &[1]),
+            (3, "b", &[]),
+            (4, "a", &[5]),
+            (5, "conn", &[]),
+            (6, "z", &[]),
+        ]))
+        .unwrap();
+        assert_eq!(
+            order,
+            [3, 5, 4, 1, 2, 6]
+                .into_iter()
+                .map(GlobalId::User)
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            dependency_order(&objects(&[
+                (1, "x", &[2]),
+                (2, "y", &[1]),
+                (3, "w", &[2]),
+                (4, "t", &[]),
+            ])),
+            Err(vec!["w".to_string(), "x".to_string(), "y".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_show_columns_query() {
+        let (query, order, projection) = show_columns_query(GlobalId::User(1), false);
+        assert!(query.contains("mz_columns.position\n"));
+        assert!(query.contains("WHERE mz_columns.id = 'u1'"));
+        assert!(!query.contains("mz_types"));
+        assert_eq!(order, "position");
+        assert_eq!(projection, ["name", "nullable", "type"]);
+
+        // The full listing only adds columns, ordered by position as well.
+        let (full_query, full_order, full_projection) = show_columns_query(GlobalId::User(1), true);
+        assert!(full_query.contains("mz_columns.position AS field_number"));
+        assert!(full_query.contains("mz_types.category"));
+        assert!(full_query.contains("WHERE mz_columns.id = 'u1'"));
+        assert_eq!(full_order, "field_number");
+        assert_eq!(full_projection[..projection.len()], *projection);
+        assert_eq!(
+            full_projection,
+            ["name", "nullable", "type", "field_number", "category"]
+        );
+    }
+
+    #[test]
+    fn test_schema_predicate() {
+        let schema_spec = SchemaSpecifier::Id(SchemaId(3));
+        assert_eq!(schema_predicate(&schema_spec, false), "schema_id = 3");
+
+        // The extended listing broadens the predicate to the system schemas,
+        // which belong to no database, and composes with other predicates.
+        let extended = schema_predicate(&schema_spec, true);
+        assert_eq!(
+            extended,
+            "(schema_id = 3 OR schema_id IN \
+            (SELECT id FROM mz_catalog.mz_schemas WHERE database_id IS NULL))"
+        );
+        assert_eq!(
+            schema_predicate(&SchemaSpecifier::Temporary, false),
+            "schema_id = 0"
+        );
+    }
+
+    #[test]
+    fn test_full_sinks_columns() {
+        // The columns the full sink listing adds read the catalog by fully
+        // qualified names, as the listings do not rely on the search path.
+        let status = latest_status("mz_sink_status_history", "sink_id", "sinks.id");
+        assert!(status.contains("FROM mz_internal.mz_sink_status_history"));
+        assert!(status.contains("WHERE sink_id = sinks.id"));
+
+        let created_at = created_at_column(true, "'sink'", "sinks.id");
+        assert!(created_at.contains("FROM mz_catalog.mz_audit_events"));
+        assert!(created_at.contains("object_type = 'sink'"));
+        assert!(created_at.ends_with("AS created_at"));
+        assert_eq!(created_at_column(false, "'sink'", "sinks.id"), "");
+    }
+
+    #[test]
+    fn test_created_at_order() {
+        assert_eq!(created_at_order(None), None);
+        assert_eq!(
+            created_at_order(Some(true)),
+            Some("created_at NULLS LAST, name")
+        );
+        assert_eq!(
+            created_at_order(Some(false)),
+            Some("created_at DESC NULLS LAST, name")
+        );
+
+        // The order applies to the full listing, once wrapped as
+        // `ShowSelect::new` does.
+        let created_at = created_at_column(true, "'table'", "mz_tables.id");
+        let order = created_at_order(Some(false)).unwrap();
+        let query = format!(
+            "SELECT * FROM (SELECT name{created_at} FROM mz_catalog.mz_tables) q \
+            WHERE true ORDER BY {order}"
+        );
+        assert!(parse::parse(&query).is_ok(), "{query}");
+    }
+
+    #[test]
+    fn test_source_and_sink_queries() {
+        // Every combination of the columns of the full and verbose listings
+        // must produce a valid query, once wrapped as `ShowSelect::new` does.
+        let schema_spec = SchemaSpecifier::Id(SchemaId(3));
+        for full in [false, true] {
+            for verbose in [false, true] {
+                for query in [
+                    sources_query(&schema_spec, full, verbose),
+                    sinks_query(&schema_spec, full, verbose),
+                ] {
+                    assert!(query.ends_with("WHERE schema_id = 3"), "{query}");
+                    let query = format!("SELECT * FROM ({query}) q WHERE true ORDER BY q.*");
+                    assert!(parse::parse(&query).is_ok(), "{query}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_show_select_invalid_sql() {
+        let scx = StatementContext::new(None, &DummyCatalog);
+        let show_select = |query: &str| ShowSelect::new(&scx, query.into(), None, None, None);
+
+        // Invalid queries are errors rather than panics.
+        let err = match show_select("SELECT name, FROM t") {
+            Ok(_) => panic!("a query with a dangling comma is planned"),
+            Err(err) => err.to_string(),
+        };
+        assert!(err.contains("found reserved keyword"), "{err}");
+
+        let err = match show_select("SELECT 1 AS name) q; SELECT * FROM (SELECT 1") {
+            Ok(_) => panic!("a query of several statements is planned"),
+            Err(err) => err.to_string(),
+        };
+        assert!(err.contains("non-SELECT query"), "{err}");
+    }
+
+    #[test]
+    fn test_show_select_json_format_invalid_body() {
+        let scx = StatementContext::new(None, &DummyCatalog);
+        let show_select = |body| ShowSelect {
+            scx: &scx,
+            stmt: SelectStatement {
+                query: Query {
+                    ctes: vec![],
+                    body,
+                    order_by: vec![],
+                    limit: None,
+                    offset: None,
+                },
+                as_of: None,
+            },
+        };
+
+        // A body other than a `SELECT` is an internal error.
+        let err = match show_select(SetExpr::Values(Values(vec![]))).with_json_format() {
+            Ok(_) => panic!("a VALUES body is formatted as JSON"),
+            Err(err) => err.to_string(),
+        };
+        assert!(err.contains("non-SELECT body"), "{err}");
+
+        // As is a projection that is not a plain column name.
+        let select = Select {
+            distinct: None,
+            projection: vec![SelectItem::Expr {
+                expr: Expr::Value(Value::Number("1".into())),
+                alias: None,
+            }],
+            from: vec![],
+            selection: None,
+            group_by: vec![],
+            having: None,
+            options: vec![],
+        };
+        let err = match show_select(SetExpr::Select(Box::new(select))).with_json_format() {
+            Ok(_) => panic!("a computed projection is formatted as JSON"),
+            Err(err) => err.to_string(),
+        };
+        assert!(err.contains("not a column name: 1"), "{err}");
+    }
+}